@@ -79,6 +79,7 @@ distr_int!(distr_uniform_i8, i8, Uniform::new(20i8, 100));
 distr_int!(distr_uniform_i16, i16, Uniform::new(-500i16, 2000));
 distr_int!(distr_uniform_i32, i32, Uniform::new(-200_000_000i32, 800_000_000));
 distr_int!(distr_uniform_i64, i64, Uniform::new(3i64, 123_456_789_123));
+distr_int!(distr_uniform_i32_symmetric, i32, Uniform::<i32>::new_symmetric(500_000_000));
 #[cfg(feature = "i128_support")]
 distr_int!(distr_uniform_i128, i128, Uniform::new(-123_456_789_123i128, 123_456_789_123_456_789));
 
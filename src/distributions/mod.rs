@@ -171,13 +171,16 @@
 //! [`Uniform::new_inclusive`]: struct.Uniform.html#method.new_inclusive
 //! [`WeightedIndex`]: struct.WeightedIndex.html
 
-use Rng;
+use {Rng, RngCore};
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::boxed::Box;
 
 #[doc(inline)] pub use self::other::Alphanumeric;
 #[doc(inline)] pub use self::uniform::Uniform;
 #[doc(inline)] pub use self::float::{OpenClosed01, Open01};
 #[cfg(feature="alloc")]
 #[doc(inline)] pub use self::weighted::WeightedIndex;
+#[cfg(feature="alloc")]
+#[doc(inline)] pub use self::weighted::DynamicWeightedIndex;
 #[cfg(feature="std")]
 #[doc(inline)] pub use self::gamma::{Gamma, ChiSquared, FisherF, StudentT};
 #[cfg(feature="std")]
@@ -286,6 +289,36 @@ impl<'a, T, D: Distribution<T>> Distribution<T> for &'a D {
     }
 }
 
+/// An object-safe counterpart of [`Distribution`].
+///
+/// `Distribution::sample` is generic over the RNG, so `Distribution` cannot
+/// be used as a trait object. `DynDistribution` is implemented for every
+/// `Distribution` and takes the RNG as `&mut RngCore` instead, which allows
+/// distributions of different types to be stored behind a single pointer
+/// type. A `Box<DynDistribution<T> + Send + Sync>` implements `Distribution`
+/// again.
+///
+/// [`Distribution`]: trait.Distribution.html
+pub trait DynDistribution<T> {
+    /// Generate a random value of `T`, using `rng` as the source of randomness.
+    fn sample_dyn(&self, rng: &mut RngCore) -> T;
+}
+
+impl<T, D: Distribution<T>> DynDistribution<T> for D {
+    fn sample_dyn(&self, rng: &mut RngCore) -> T {
+        self.sample(rng)
+    }
+}
+
+#[cfg(feature="alloc")]
+impl<T> Distribution<T> for Box<DynDistribution<T> + Send + Sync> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        // `R` may be unsized; `&mut R` implements `RngCore` and is sized.
+        let mut rng = rng;
+        (**self).sample_dyn(&mut rng)
+    }
+}
+
 
 /// An iterator that generates random values of `T` with distribution `D`,
 /// using `R` as the source of randomness.
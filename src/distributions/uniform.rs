@@ -16,14 +16,15 @@
 //! [`Uniform`].
 //!
 //! This distribution is provided with support for several primitive types
-//! (all integer and floating-point types) as well as `std::time::Duration`,
-//! and supports extension to user-defined types via a type-specific *back-end*
-//! implementation.
+//! (all integer and floating-point types and `char`) as well as
+//! `std::time::Duration`, and supports extension to user-defined types via a
+//! type-specific *back-end* implementation.
 //!
-//! The types [`UniformInt`], [`UniformFloat`] and [`UniformDuration`] are the
-//! back-ends supporting sampling from primitive integer and floating-point
-//! ranges as well as from `std::time::Duration`; these types do not normally
-//! need to be used directly (unless implementing a derived back-end).
+//! The types [`UniformInt`], [`UniformFloat`], [`UniformChar`] and
+//! [`UniformDuration`] are the back-ends supporting sampling from primitive
+//! integer, floating-point and `char` ranges as well as from
+//! `std::time::Duration`; these types do not normally need to be used directly
+//! (unless implementing a derived back-end).
 //!
 //! # Example usage
 //!
@@ -107,16 +108,44 @@
 //! [`UniformSampler`]: trait.UniformSampler.html
 //! [`UniformInt`]: struct.UniformInt.html
 //! [`UniformFloat`]: struct.UniformFloat.html
+//! [`UniformChar`]: struct.UniformChar.html
 //! [`UniformDuration`]: struct.UniformDuration.html
 //! [`Borrow::borrow`]: trait.SampleBorrow.html
 
 #[cfg(feature = "std")]
 use std::time::Duration;
+#[cfg(feature = "nonzero_support")]
+use core::num::NonZeroUsize;
+#[cfg(feature = "maybe_uninit_support")]
+use core::mem::MaybeUninit;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+use std::net::Ipv4Addr;
+#[cfg(all(feature = "core_net_support", not(feature = "std")))]
+use core::net::Ipv4Addr;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::Vec;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::btree_set::BTreeSet;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::boxed::Box;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::String;
 
-use Rng;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::Bound;
+use {Rng, RngCore, SeedableRng, Error};
+use rand_core::impls;
+use prng::ChaChaRng;
 use distributions::Distribution;
+#[cfg(feature = "alloc")]
+use distributions::DynDistribution;
+#[cfg(feature = "alloc")]
+use distributions::WeightedIndex;
 use distributions::float::IntoFloat;
-use distributions::utils::{WideningMultiply, CompareAll};
+use distributions::utils::{WideningMultiply, CompareAll, FloatAsOrderedInt};
 
 #[cfg(feature="simd_support")]
 use core::simd::*;
@@ -187,6 +216,146 @@ impl<X: SampleUniform> Uniform<X> {
     {
         Uniform { inner: X::Sampler::new_inclusive(low, high) }
     }
+
+    /// Discard `skip` `u64` words from `rng`, then sample a value.
+    ///
+    /// This is a simple way to start several workers sharing the same seed
+    /// at different offsets in the RNG's stream. Note that streams started at
+    /// nearby offsets overlap.
+    pub fn sample_after_skip<R: Rng + ?Sized>(&self, rng: &mut R, skip: u64) -> X {
+        for _ in 0..skip {
+            rng.gen::<u64>();
+        }
+        self.inner.sample(rng)
+    }
+
+    /// Box this distribution as a [`DynDistribution`] trait object, erasing
+    /// the type of the back-end.
+    ///
+    /// The result implements `Distribution<X>`, is `Send` and `Sync`, and can
+    /// be stored alongside other boxed distributions producing `X`.
+    ///
+    /// [`DynDistribution`]: ../trait.DynDistribution.html
+    #[cfg(feature = "alloc")]
+    pub fn boxed(self) -> Box<DynDistribution<X> + Send + Sync>
+        where X: 'static, X::Sampler: Send + Sync + 'static
+    {
+        Box::new(self)
+    }
+
+    /// Create an iterator of samples using `rng` as the source of randomness.
+    ///
+    /// This is like [`Distribution::sample_iter`], but the returned
+    /// [`UniformIter`] only has a lifetime and two type parameters, making it
+    /// easier to name, e.g. to store it in a struct.
+    ///
+    /// [`Distribution::sample_iter`]: ../trait.Distribution.html#method.sample_iter
+    /// [`UniformIter`]: struct.UniformIter.html
+    pub fn iter<'a, R: Rng + ?Sized>(&'a self, rng: &'a mut R) -> UniformIter<'a, X, R> {
+        UniformIter {
+            sampler: &self.inner,
+            rng: rng,
+        }
+    }
+
+    /// Create an iterator over the samples of worker `i` of `k` partitioning
+    /// the stream of samples from `rng` by leapfrogging: the worker takes
+    /// samples `i`, `i + k`, `i + 2k`, etc.
+    ///
+    /// If all `k` workers use clones of the same RNG, together they see
+    /// exactly the samples of a single stream, reproducibly and without
+    /// separate seeds. Each worker still has to generate and discard the
+    /// samples of all other workers, so this is only cheap for small `k`.
+    /// Note that the streams are disjoint, not independent: any correlation
+    /// in the RNG's output at lag `k` becomes a correlation within a stream.
+    ///
+    /// Panics if `k == 0` or `i >= k`.
+    pub fn leapfrog<'a, R: Rng + ?Sized>(&'a self, rng: &'a mut R, k: usize, i: usize)
+        -> Leapfrog<'a, X, R>
+    {
+        assert!(i < k, "Uniform::leapfrog called with `i >= k`");
+        Leapfrog {
+            sampler: &self.inner,
+            rng: rng,
+            k: k,
+            skip: i,
+        }
+    }
+
+    /// Bind `rng` to the distribution, so that values can be sampled with
+    /// [`BoundUniform::next`] without passing the RNG each time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::distributions::Uniform;
+    ///
+    /// let mut rng = thread_rng();
+    /// let die = Uniform::new_inclusive(1, 6);
+    /// let mut roll = die.with_rng(&mut rng);
+    /// let total = roll.next() + roll.next();
+    /// assert!(2 <= total && total <= 12);
+    /// ```
+    ///
+    /// [`BoundUniform::next`]: struct.BoundUniform.html#method.next
+    pub fn with_rng<'a, R: Rng + ?Sized>(&'a self, rng: &'a mut R) -> BoundUniform<'a, X, R> {
+        BoundUniform {
+            sampler: &self.inner,
+            rng: rng,
+        }
+    }
+
+    /// Restrict the distribution to values for which `pred` returns `true`.
+    ///
+    /// The returned [`FilteredUniform`] samples values until one is
+    /// accepted, so the values are distributed uniformly over the accepted
+    /// values. If `pred` accepts only a small fraction of the range, sampling
+    /// is slow; if it accepts no values at all, sampling never terminates.
+    /// In the latter case use a loop with a cap on the number of retries
+    /// instead.
+    ///
+    /// [`FilteredUniform`]: struct.FilteredUniform.html
+    pub fn filter<F: Fn(&X) -> bool>(self, pred: F) -> FilteredUniform<X, F> {
+        FilteredUniform {
+            sampler: self.inner,
+            pred: pred,
+        }
+    }
+}
+
+impl<X: SampleUniform> Uniform<X> where X::Sampler: Clone {
+    /// Create an infinite iterator of samples using a new RNG of type `R`,
+    /// for example to give each task of a parallel computation its own
+    /// reproducible stream.
+    ///
+    /// The RNG is seeded by hashing `base_seed` and `task_id`: the same pair
+    /// always yields the same samples, and different `task_id`s yield
+    /// unrelated ones. Note that the hash is not cryptographically secure, and
+    /// that the streams are only as independent as `R` allows.
+    pub fn stream<R: SeedableRng + Rng>(&self, base_seed: u64, task_id: u64)
+        -> UniformStream<X, R>
+    {
+        UniformStream {
+            sampler: self.inner.clone(),
+            rng: seed_from_pair(base_seed, task_id),
+        }
+    }
+}
+
+impl<X: SampleUniform> Uniform<X> {
+    /// Sample a single value, reproducibly, using an RNG seeded from `seed`.
+    ///
+    /// This is a convenience for examples and tests; the same `seed` always
+    /// yields the same value. The RNG used is [`ChaChaRng`], whose output is
+    /// not expected to change, although the way the seed is expanded into a
+    /// `ChaChaRng` seed is not guaranteed to stay the same between versions.
+    ///
+    /// [`ChaChaRng`]: ../../prng/chacha/struct.ChaChaRng.html
+    pub fn sample_seeded(&self, seed: u64) -> X {
+        let mut rng: ChaChaRng = seed_from_pair(seed, 0);
+        self.inner.sample(&mut rng)
+    }
 }
 
 impl<X: SampleUniform> Distribution<X> for Uniform<X> {
@@ -195,6 +364,267 @@ impl<X: SampleUniform> Distribution<X> for Uniform<X> {
     }
 }
 
+/// A [`Uniform`] distribution restricted to values accepted by a predicate.
+///
+/// This `struct` is created by the [`Uniform::filter`] method. See its
+/// documentation for more.
+///
+/// [`Uniform`]: struct.Uniform.html
+/// [`Uniform::filter`]: struct.Uniform.html#method.filter
+#[derive(Clone, Copy, Debug)]
+pub struct FilteredUniform<X: SampleUniform, F> {
+    sampler: X::Sampler,
+    pred: F,
+}
+
+impl<X: SampleUniform, F: Fn(&X) -> bool> Distribution<X> for FilteredUniform<X, F> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> X {
+        loop {
+            let x = self.sampler.sample(rng);
+            if (self.pred)(&x) {
+                return x;
+            }
+        }
+    }
+}
+
+/// A sampler from a [`Uniform`] distribution which avoids repeating any of the
+/// last `k` values it returned, e.g. for procedural generation.
+///
+/// The recent values are kept in a ring buffer; samples which are in it are
+/// rejected. As it has state, this is not a [`Distribution`]; sample with
+/// `NoRepeat::sample` instead.
+///
+/// It is constructed with `NoRepeat::new`, which is available for integer
+/// types and requires the range to contain more than `k` values.
+///
+/// [`Uniform`]: struct.Uniform.html
+/// [`Distribution`]: ../trait.Distribution.html
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct NoRepeat<X: SampleUniform> {
+    sampler: X::Sampler,
+    recent: Vec<X>,
+    k: usize,
+    next: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<X: SampleUniform + PartialEq + Clone> NoRepeat<X> {
+    /// Sample a value which is not equal to any of the last `k` values
+    /// returned.
+    pub fn sample<R: Rng + ?Sized>(&mut self, rng: &mut R) -> X {
+        let x = loop {
+            let x = self.sampler.sample(rng);
+            if !self.recent.contains(&x) {
+                break x;
+            }
+        };
+        if self.recent.len() < self.k {
+            self.recent.push(x.clone());
+        } else if self.k > 0 {
+            self.recent[self.next] = x.clone();
+            self.next = (self.next + 1) % self.k;
+        }
+        x
+    }
+}
+
+/// A distribution stitched together from several uniform segments, each
+/// chosen with a probability proportional to its weight.
+///
+/// The segments do not need to be contiguous and may overlap; the resulting
+/// density is piecewise constant. A segment is chosen with a
+/// [`WeightedIndex`], then a value is sampled from it.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Uniform};
+/// use rand::distributions::uniform::PiecewiseUniform;
+///
+/// // Mostly small values, but occasionally a large one.
+/// let distr = PiecewiseUniform::new(&[(Uniform::new(0, 10), 9.0),
+///                                     (Uniform::new(1000, 2000), 1.0)]);
+/// let x = distr.sample(&mut rand::thread_rng());
+/// assert!(x < 10 || (1000 <= x && x < 2000));
+/// ```
+///
+/// [`WeightedIndex`]: ../struct.WeightedIndex.html
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct PiecewiseUniform<X: SampleUniform> {
+    segments: Vec<X::Sampler>,
+    index: WeightedIndex<f64>,
+}
+
+#[cfg(feature = "alloc")]
+impl<X: SampleUniform> PiecewiseUniform<X> where X::Sampler: Clone {
+    /// Create a new `PiecewiseUniform` from pairs of segments and weights.
+    ///
+    /// Panics if `segments` is empty or if any weight is not positive.
+    pub fn new(segments: &[(Uniform<X>, f64)]) -> PiecewiseUniform<X> {
+        assert!(!segments.is_empty(), "PiecewiseUniform::new called with no segments");
+        assert!(segments.iter().all(|&(_, w)| w > 0.0),
+                "PiecewiseUniform::new called with a weight which is not positive");
+        PiecewiseUniform {
+            segments: segments.iter().map(|&(ref s, _)| s.inner.clone()).collect(),
+            index: WeightedIndex::new(segments.iter().map(|&(_, w)| w)).unwrap(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<X: SampleUniform> Distribution<X> for PiecewiseUniform<X> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> X {
+        self.segments[self.index.sample(rng)].sample(rng)
+    }
+}
+
+/// A distribution sampling uniformly from `n` evenly spaced floating-point
+/// levels in `[low, high]`, including both endpoints.
+///
+/// This `struct` is created by the `Uniform::new_levels` method, which is
+/// available for `f32` and `f64`.
+#[derive(Clone, Copy, Debug)]
+pub struct UniformLevels<X> {
+    low: X,
+    high: X,
+    step: X,
+    index: UniformInt<usize>,
+}
+
+/// A [`Uniform`] distribution bound to an RNG.
+///
+/// This `struct` is created by the [`Uniform::with_rng`] method. See its
+/// documentation for more.
+///
+/// [`Uniform`]: struct.Uniform.html
+/// [`Uniform::with_rng`]: struct.Uniform.html#method.with_rng
+#[derive(Debug)]
+pub struct BoundUniform<'a, X: SampleUniform + 'a, R: ?Sized + 'a> {
+    sampler: &'a X::Sampler,
+    rng: &'a mut R,
+}
+
+impl<'a, X: SampleUniform, R: Rng + ?Sized> BoundUniform<'a, X, R> {
+    /// Sample a value using the bound RNG.
+    #[inline]
+    pub fn next(&mut self) -> X {
+        self.sampler.sample(self.rng)
+    }
+}
+
+/// An infinite iterator of samples from a [`Uniform`] distribution, borrowing
+/// the distribution and an RNG.
+///
+/// This `struct` is created by the [`Uniform::iter`] method. See its
+/// documentation for more.
+///
+/// [`Uniform`]: struct.Uniform.html
+/// [`Uniform::iter`]: struct.Uniform.html#method.iter
+#[derive(Debug)]
+pub struct UniformIter<'a, X: SampleUniform + 'a, R: ?Sized + 'a> {
+    sampler: &'a X::Sampler,
+    rng: &'a mut R,
+}
+
+impl<'a, X: SampleUniform, R: Rng + ?Sized> Iterator for UniformIter<'a, X, R> {
+    type Item = X;
+
+    #[inline]
+    fn next(&mut self) -> Option<X> {
+        Some(self.sampler.sample(self.rng))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::max_value(), None)
+    }
+}
+
+/// An infinite iterator over every `k`-th sample from a [`Uniform`]
+/// distribution.
+///
+/// This `struct` is created by the [`Uniform::leapfrog`] method. See its
+/// documentation for more.
+///
+/// [`Uniform`]: struct.Uniform.html
+/// [`Uniform::leapfrog`]: struct.Uniform.html#method.leapfrog
+#[derive(Debug)]
+pub struct Leapfrog<'a, X: SampleUniform + 'a, R: ?Sized + 'a> {
+    sampler: &'a X::Sampler,
+    rng: &'a mut R,
+    k: usize,
+    skip: usize,
+}
+
+impl<'a, X: SampleUniform, R: Rng + ?Sized> Iterator for Leapfrog<'a, X, R> {
+    type Item = X;
+
+    fn next(&mut self) -> Option<X> {
+        // Samples are discarded by sampling them, as skipping RNG output
+        // would not skip the same number of samples for rejection sampling.
+        for _ in 0..self.skip {
+            self.sampler.sample(self.rng);
+        }
+        self.skip = self.k - 1;
+        Some(self.sampler.sample(self.rng))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::max_value(), None)
+    }
+}
+
+/// An infinite iterator of samples from a [`Uniform`] distribution, with its
+/// own RNG.
+///
+/// This `struct` is created by the [`Uniform::stream`] method. See its
+/// documentation for more.
+///
+/// [`Uniform`]: struct.Uniform.html
+/// [`Uniform::stream`]: struct.Uniform.html#method.stream
+#[derive(Debug)]
+pub struct UniformStream<X: SampleUniform, R> {
+    sampler: X::Sampler,
+    rng: R,
+}
+
+impl<X: SampleUniform, R: Rng> Iterator for UniformStream<X, R> {
+    type Item = X;
+
+    #[inline]
+    fn next(&mut self) -> Option<X> {
+        Some(self.sampler.sample(&mut self.rng))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::max_value(), None)
+    }
+}
+
+/// Construct an RNG with a seed derived by hashing `a` and `b`.
+fn seed_from_pair<R: SeedableRng>(a: u64, b: u64) -> R {
+    let mut seed = R::Seed::default();
+    let mut state = mix64(mix64(a).wrapping_add(b));
+    for chunk in seed.as_mut().chunks_mut(8) {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let v = mix64(state);
+        for (i, b) in chunk.iter_mut().enumerate() {
+            *b = (v >> (i * 8)) as u8;
+        }
+    }
+    R::from_seed(seed)
+}
+
+/// The finalizer of SplitMix64: a bijective function with good avalanche
+/// properties, used to derive seeds.
+fn mix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
 /// Helper trait for creating objects using the correct implementation of
 /// [`UniformSampler`] for the sampling type.
 ///
@@ -272,6 +702,14 @@ impl<X: SampleUniform> From<::core::ops::Range<X>> for Uniform<X> {
     }
 }
 
+#[cfg(feature = "range_inclusive_support")]
+impl<X: SampleUniform> From<::core::ops::RangeInclusive<X>> for Uniform<X> {
+    fn from(r: ::core::ops::RangeInclusive<X>) -> Uniform<X> {
+        let (low, high) = r.into_inner();
+        Uniform::new_inclusive(low, high)
+    }
+}
+
 /// Helper trait similar to [`Borrow`] but implemented
 /// only for SampleUniform and references to SampleUniform in
 /// order to resolve ambiguity issues.
@@ -332,6 +770,11 @@ impl<'a, Borrowed> SampleBorrow<Borrowed> for &'a Borrowed where Borrowed: Sampl
 /// multiply by `range`, the result is in the high word. Then comparing the low
 /// word against `zone` makes sure our distribution is uniform.
 ///
+/// Taking the result from the high word means it depends mostly on the high
+/// bits of the random number, which is why RNGs with weak low bits, such as
+/// linear congruential generators, still give good results. A modulus would
+/// instead depend on the low bits.
+///
 /// [`UniformSampler`]: trait.UniformSampler.html
 /// [`Uniform`]: struct.Uniform.html
 #[derive(Clone, Copy, Debug)]
@@ -341,6 +784,86 @@ pub struct UniformInt<X> {
     zone: X,
 }
 
+/// Statistics on the rejection sampling of [`UniformInt`], as returned by
+/// `UniformInt::sample_batch_stats`.
+///
+/// [`UniformInt`]: struct.UniformInt.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RejectionStats {
+    /// The total number of values drawn from the RNG.
+    pub total_draws: u64,
+    /// The number of drawn values which were rejected.
+    pub rejections: u64,
+    /// The largest number of rejections before a single sample was accepted.
+    pub max_retries: u64,
+}
+
+/// An RNG replaying a stream of recorded `u64` words, used to implement
+/// `UniformInt::sample_from_words`. Once the words run out it yields zeros and
+/// remembers that it was exhausted.
+struct WordRng<'a, I: 'a> {
+    words: &'a mut I,
+    exhausted: bool,
+}
+
+impl<'a, I: Iterator<Item = u64>> RngCore for WordRng<'a, I> {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self.words.next() {
+            Some(word) => word,
+            None => {
+                self.exhausted = true;
+                0
+            }
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        Ok(self.fill_bytes(dest))
+    }
+}
+
+/// An RNG serving words fetched in bulk from another RNG, used to implement
+/// `UniformInt::sample_prefetched`. Once the words run out it falls back to
+/// the underlying RNG.
+#[cfg(feature = "alloc")]
+struct PrefetchRng<'a, R: ?Sized + 'a> {
+    words: Vec<u64>,
+    pos: usize,
+    rng: &'a mut R,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, R: RngCore + ?Sized> RngCore for PrefetchRng<'a, R> {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        if self.pos < self.words.len() {
+            self.pos += 1;
+            self.words[self.pos - 1]
+        } else {
+            self.rng.next_u64()
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        Ok(self.fill_bytes(dest))
+    }
+}
+
 macro_rules! uniform_int_impl {
     ($ty:ty, $signed:ty, $unsigned:ident,
      $i_large:ident, $u_large:ident) => {
@@ -397,26 +920,9 @@ macro_rules! uniform_int_impl {
                 }
             }
 
+            #[inline]
             fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
-                let range = self.range as $unsigned as $u_large;
-                if range > 0 {
-                    // Grow `zone` to fit a type of at least 32 bits, by
-                    // sign-extending it (the first bit is always 1, so are all
-                    // the preceding bits of the larger type).
-                    // For types that already have the right size, all the
-                    // casting is a no-op.
-                    let zone = self.zone as $signed as $i_large as $u_large;
-                    loop {
-                        let v: $u_large = rng.gen();
-                        let (hi, lo) = v.wmul(range);
-                        if lo <= zone {
-                            return self.low.wrapping_add(hi as $ty);
-                        }
-                    }
-                } else {
-                    // Sample from the entire integer range.
-                    rng.gen()
-                }
+                self.sample_counting(rng, &mut 0)
             }
 
             fn sample_single<R: Rng + ?Sized, B1, B2>(low_b: B1, high_b: B2, rng: &mut R)
@@ -451,38 +957,715 @@ macro_rules! uniform_int_impl {
                 }
             }
         }
-    }
-}
 
-uniform_int_impl! { i8, i8, u8, i32, u32 }
-uniform_int_impl! { i16, i16, u16, i32, u32 }
-uniform_int_impl! { i32, i32, u32, i32, u32 }
-uniform_int_impl! { i64, i64, u64, i64, u64 }
-#[cfg(feature = "i128_support")]
-uniform_int_impl! { i128, i128, u128, u128, u128 }
-uniform_int_impl! { isize, isize, usize, isize, usize }
-uniform_int_impl! { u8, i8, u8, i32, u32 }
-uniform_int_impl! { u16, i16, u16, i32, u32 }
-uniform_int_impl! { u32, i32, u32, i32, u32 }
-uniform_int_impl! { u64, i64, u64, i64, u64 }
-uniform_int_impl! { usize, isize, usize, isize, usize }
-#[cfg(feature = "i128_support")]
-uniform_int_impl! { u128, u128, u128, i128, u128 }
+        impl UniformInt<$ty> {
+            /// Whether the sampler covers every value of the type. In that case
+            /// sampling needs no rejection step.
+            #[inline]
+            pub fn is_full_range(&self) -> bool {
+                self.range == 0
+            }
 
+            /// Sample a value, adding the number of RNG draws used to `draws`.
+            #[inline(always)]
+            fn sample_counting<R: Rng + ?Sized>(&self, rng: &mut R, draws: &mut u64)
+                -> $ty
+            {
+                let range = self.range as $unsigned as $u_large;
+                if range > 0 {
+                    // Grow `zone` to fit a type of at least 32 bits, by
+                    // sign-extending it (the first bit is always 1, so are all
+                    // the preceding bits of the larger type).
+                    // For types that already have the right size, all the
+                    // casting is a no-op.
+                    let zone = self.zone as $signed as $i_large as $u_large;
+                    loop {
+                        *draws += 1;
+                        let v: $u_large = rng.gen();
+                        let (hi, lo) = v.wmul(range);
+                        if lo <= zone {
+                            return self.low.wrapping_add(hi as $ty);
+                        }
+                    }
+                } else {
+                    // Sample from the entire integer range.
+                    *draws += 1;
+                    rng.gen()
+                }
+            }
 
+            /// Sample a value using a single `u32` per draw when the range
+            /// fits in 32 bits, also for 64-bit types, e.g. for natively
+            /// 32-bit RNGs which would otherwise combine two words for every
+            /// `u64`. Wider ranges are sampled as by `sample`.
+            ///
+            /// The result is uniform, but differs from that of `sample` for
+            /// the same RNG state.
+            pub fn sample_with_u32<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                let range = self.range as $unsigned as $u_large;
+                if range == 0 || range > ::core::u32::MAX as $u_large {
+                    return self.sample(rng);
+                }
+                let range = range as u32;
+                loop {
+                    let v: u32 = rng.gen();
+                    let (hi, lo) = v.wmul(range);
+                    // Fewer than `range` low words are rejected, so the exact
+                    // zone is only needed for the largest ones.
+                    if lo <= ::core::u32::MAX - (range - 1) ||
+                       lo <= ::core::u32::MAX - 0u32.wrapping_sub(range) % range {
+                        return self.low.wrapping_add(hi as $ty);
+                    }
+                }
+            }
 
-/// The back-end implementing [`UniformSampler`] for floating-point types.
-///
-/// Unless you are implementing [`UniformSampler`] for your own type, this type
-/// should not be used directly, use [`Uniform`] instead.
-///
-/// # Implementation notes
-///
-/// Instead of generating a float in the `[0, 1)` range using [`Standard`], the
-/// `UniformFloat` implementation converts the output of an PRNG itself. This
-/// way one or two steps can be optimized out.
-///
-/// The floats are first converted to a value in the `[1, 2)` interval using a
+            /// The number of bytes taken from the RNG per draw, and the
+            /// probability that a draw is accepted. This mirrors the paths
+            /// taken in `sample_counting`.
+            fn draw_bytes_and_acceptance(&self) -> (usize, f64) {
+                let range = self.range as $unsigned as $u_large;
+                if range > 0 {
+                    let zone = self.zone as $signed as $i_large as $u_large;
+                    let words = <$u_large>::max_value() as f64 + 1.0;
+                    (::core::mem::size_of::<$u_large>(), (zone as f64 + 1.0) / words)
+                } else {
+                    // `rng.gen()` consumes at least a `u32`.
+                    (::core::cmp::max(::core::mem::size_of::<$ty>(), 4), 1.0)
+                }
+            }
+
+            /// The largest value which can be sampled.
+            #[inline]
+            fn last(&self) -> $ty {
+                self.low.wrapping_add(self.range.wrapping_sub(1))
+            }
+
+            /// Whether `x` is within the range of the sampler.
+            #[inline]
+            fn contains(&self, x: $ty) -> bool {
+                x.wrapping_sub(self.low) as $unsigned
+                    <= (self.range as $unsigned).wrapping_sub(1)
+            }
+
+            /// Treat the upper bound of a `[low, high)` sampler as inclusive,
+            /// returning a sampler for `[low, high]`. If `high - 1` is already
+            /// the largest value of the type, the sampler is returned
+            /// unchanged.
+            pub fn as_inclusive(&self) -> Self {
+                let last = self.last();
+                if last == <$ty>::max_value() {
+                    *self
+                } else {
+                    UniformSampler::new_inclusive(self.low, last + 1)
+                }
+            }
+
+            /// Treat the upper bound of a `[low, high]` sampler as exclusive,
+            /// returning a sampler for `[low, high)`. Panics if this range is
+            /// empty, i.e. `low == high`.
+            pub fn as_exclusive(&self) -> Self {
+                UniformSampler::new(self.low, self.last())
+            }
+
+            /// A sampler for a range of the same width starting at `new_low`.
+            ///
+            /// This reuses the rejection zone of `self` rather than computing
+            /// it again, so is cheaper than constructing a new sampler. Panics
+            /// if the shifted range does not fit in the type.
+            pub fn shifted(&self, new_low: $ty) -> Self {
+                let range = self.range as $unsigned;
+                if range != 0 {
+                    let room = <$ty>::max_value().wrapping_sub(new_low) as $unsigned;
+                    assert!(room >= range - 1,
+                            "UniformInt::shifted called with `new_low` too large for the range");
+                }
+                UniformInt {
+                    low: new_low,
+                    range: self.range,
+                    zone: self.zone,
+                }
+            }
+
+            /// Map `x` into the range by mirror reflection at its bounds, e.g.
+            /// to keep a random walk inside the range: `high + d` maps to
+            /// `high - d`, and `low - d` to `low + d`. Values further away are
+            /// folded repeatedly, like a triangle wave.
+            pub fn reflect(&self, x: $ty) -> $ty {
+                if self.range == 0 {
+                    return x;
+                }
+                let width = (self.range as $unsigned) - 1;
+                if width == 0 {
+                    return self.low;
+                }
+                // Reflection is symmetric around `low`, so only the distance
+                // to it matters.
+                let d = if x >= self.low {
+                    x.wrapping_sub(self.low) as $unsigned
+                } else {
+                    self.low.wrapping_sub(x) as $unsigned
+                };
+                let t = match width.checked_mul(2) {
+                    Some(period) => d % period,
+                    None => d,
+                };
+                let t = if t > width { width - (t - width) } else { t };
+                self.low.wrapping_add(t as $ty)
+            }
+
+            /// Sample a value differing from `current` in a single bit, e.g.
+            /// for local search heuristics.
+            ///
+            /// A random bit of `current` is flipped; if the result is outside
+            /// the range another bit is tried. After a fixed number of failed
+            /// attempts this falls back to a plain sample, which need not be
+            /// a neighbour of `current`.
+            pub fn sample_neighbor<R: Rng + ?Sized>(&self, rng: &mut R, current: $ty) -> $ty {
+                const MAX_TRIES: usize = 64;
+                let bits = ::core::mem::size_of::<$ty>() * 8;
+                for _ in 0..MAX_TRIES {
+                    let bit = rng.gen_range(0, bits);
+                    let x = current ^ ((1 as $unsigned) << bit) as $ty;
+                    if self.contains(x) {
+                        return x;
+                    }
+                }
+                self.sample(rng)
+            }
+
+            /// Fill a buffer of possibly uninitialized memory with samples,
+            /// avoiding the cost of initializing it first, e.g. for
+            /// multi-megabyte buffers.
+            ///
+            /// Every element of `dst` is written, so once this returns the
+            /// caller may soundly call `assume_init` on all of them. Values
+            /// previously in `dst` are overwritten without being dropped.
+            #[cfg(feature = "maybe_uninit_support")]
+            pub fn sample_into_uninit<R: Rng + ?Sized>(&self, rng: &mut R,
+                                                       dst: &mut [MaybeUninit<$ty>])
+            {
+                for x in dst.iter_mut() {
+                    *x = MaybeUninit::new(self.sample(rng));
+                }
+            }
+
+            /// The number of bytes a single draw takes from the RNG.
+            ///
+            /// Because of rejection sampling a sample may need several draws;
+            /// there is no upper bound on the total. See
+            /// [`expected_bytes_per_sample`] for the average.
+            ///
+            /// [`expected_bytes_per_sample`]: #method.expected_bytes_per_sample
+            pub fn max_bytes_per_sample(&self) -> usize {
+                self.draw_bytes_and_acceptance().0
+            }
+
+            /// The expected number of bytes a sample takes from the RNG,
+            /// accounting for rejected draws: the bytes per draw divided by
+            /// the probability that a draw is accepted.
+            pub fn expected_bytes_per_sample(&self) -> f64 {
+                let (bytes, acceptance) = self.draw_bytes_and_acceptance();
+                bytes as f64 / acceptance
+            }
+
+            /// The value at quantile `q` of the distribution, i.e. the inverse
+            /// of its cumulative distribution function: `low + floor(q * n)`
+            /// where `n` is the number of values in the range. Panics if `q` is
+            /// not in `[0, 1)`.
+            ///
+            /// Together with a source of well-spread quantiles, e.g. a
+            /// low-discrepancy sequence, this can be used for stratified
+            /// sampling.
+            pub fn quantile(&self, q: f64) -> $ty {
+                assert!(0.0 <= q && q < 1.0,
+                        "UniformInt::quantile called with `q` outside [0, 1)");
+                let range = self.range as $unsigned;
+                let n = if range == 0 {
+                    <$unsigned>::max_value() as f64 + 1.0
+                } else {
+                    range as f64
+                };
+                // `q * n` may round up to `n` for ranges larger than 2^53.
+                let offset = q * n;
+                let offset = if offset < n {
+                    offset as $unsigned
+                } else {
+                    range.wrapping_sub(1)
+                };
+                self.low.wrapping_add(offset as $ty)
+            }
+
+            /// The cumulative distribution function: the probability that a
+            /// sample is at most `x`, i.e. the fraction of the values in the
+            /// range which are `<= x`.
+            ///
+            /// This is the inverse of [`quantile`], and can e.g. be used to
+            /// test a sampler via the probability integral transform.
+            ///
+            /// [`quantile`]: #method.quantile
+            pub fn cdf(&self, x: $ty) -> f64 {
+                if x < self.low {
+                    return 0.0;
+                }
+                let range = self.range as $unsigned;
+                let offset = x.wrapping_sub(self.low) as $unsigned;
+                if offset >= range.wrapping_sub(1) {
+                    return 1.0;
+                }
+                let n = if range == 0 {
+                    <$unsigned>::max_value() as f64 + 1.0
+                } else {
+                    range as f64
+                };
+                (offset as f64 + 1.0) / n
+            }
+
+            /// The probability mass of each value in the range, `1 / n` where
+            /// `n` is the number of values, e.g. for computing acceptance
+            /// ratios when using this distribution as a proposal.
+            pub fn pdf(&self) -> f64 {
+                let range = self.range as $unsigned;
+                if range == 0 {
+                    1.0 / (<$unsigned>::max_value() as f64 + 1.0)
+                } else {
+                    1.0 / range as f64
+                }
+            }
+
+            /// The index of the bin `x` falls into when the range is divided
+            /// into `bins` bins of (nearly) equal width, e.g. for building a
+            /// histogram. Values outside the range are put in the first or
+            /// last bin. Panics if `bins == 0`.
+            pub fn bucket_of(&self, x: $ty, bins: usize) -> usize {
+                assert!(bins > 0, "UniformInt::bucket_of called with `bins == 0`");
+                if x < self.low {
+                    return 0;
+                }
+                let range = self.range as $unsigned;
+                let n = if range == 0 {
+                    <$unsigned>::max_value() as f64 + 1.0
+                } else {
+                    range as f64
+                };
+                let offset = x.wrapping_sub(self.low) as $unsigned as f64;
+                let bin = offset / n * bins as f64;
+                if bin < bins as f64 { bin as usize } else { bins - 1 }
+            }
+
+            /// Sample an offset uniformly from `[0, modulus)` and add it to
+            /// `base` modulo `modulus`, e.g. for probing a hash table. The
+            /// result is always in `[0, modulus)`, also for negative values.
+            ///
+            /// This is an associated function rather than a method: reducing a
+            /// sample from a range whose width is not a multiple of `modulus`
+            /// would not be uniform, so the offset is sampled directly.
+            ///
+            /// Panics if `modulus <= 0`.
+            pub fn sample_modular<R: Rng + ?Sized>(rng: &mut R, base: $ty, modulus: $ty)
+                -> $ty
+            {
+                let zero: $ty = 0;
+                assert!(modulus > zero, "UniformInt::sample_modular called with `modulus <= 0`");
+                let euclid_rem = |x: $ty| {
+                    let r = x % modulus;
+                    if r < zero { r + modulus } else { r }
+                };
+                let a = euclid_rem(base);
+                let b = UniformInt::<$ty>::sample_single(zero, modulus, rng);
+                // `a + b` may overflow.
+                if a >= modulus - b { a - (modulus - b) } else { a + b }
+            }
+
+            /// Sample two distinct values, e.g. for tournament selection.
+            /// Panics if the range contains fewer than two values.
+            ///
+            /// The second value is sampled from a range one smaller, and
+            /// shifted up past the first value, so no rejection is needed
+            /// beyond that of sampling the values themselves.
+            pub fn sample_two_distinct<R: Rng + ?Sized>(&self, rng: &mut R) -> ($ty, $ty) {
+                // A range of 0 represents the full range of the type.
+                let range = self.range as $unsigned;
+                assert!(range != 1,
+                        "UniformInt::sample_two_distinct called with a single-value range");
+                let x = self.sample(rng);
+                let x_offset = x.wrapping_sub(self.low) as $unsigned;
+                let mut y_offset = UniformInt::<$unsigned>::sample_single(
+                    0, range.wrapping_sub(1), rng);
+                if y_offset >= x_offset {
+                    y_offset += 1;
+                }
+                (x, self.low.wrapping_add(y_offset as $ty))
+            }
+
+            /// Fill `out[..n]` with samples, returning statistics on how many
+            /// values drawn from the RNG were rejected. Panics if
+            /// `n > out.len()`.
+            ///
+            /// This can be used to monitor for ranges with a high rejection
+            /// rate; the rate is below one half for any range.
+            pub fn sample_batch_stats<R: Rng + ?Sized>(&self, rng: &mut R, n: usize,
+                                                       out: &mut [$ty])
+                -> RejectionStats
+            {
+                let mut stats = RejectionStats::default();
+                for x in out[..n].iter_mut() {
+                    let mut draws = 0;
+                    *x = self.sample_counting(rng, &mut draws);
+                    stats.total_draws += draws;
+                    stats.rejections += draws - 1;
+                    stats.max_retries = ::core::cmp::max(stats.max_retries, draws - 1);
+                }
+                stats
+            }
+
+            /// Sample a value using a recorded stream of RNG output instead of
+            /// an RNG, e.g. to replay a run deterministically.
+            ///
+            /// Each word is consumed as if it was returned by
+            /// `RngCore::next_u64`; where a 32-bit value is needed, the low 32
+            /// bits of the word are used. Further words are taken when a value
+            /// is rejected. Returns `None` if `words` runs out before a value is
+            /// accepted.
+            pub fn sample_from_words<I>(&self, words: &mut I) -> Option<$ty>
+                where I: Iterator<Item = u64>
+            {
+                let mut rng = WordRng { words: words, exhausted: false };
+                let value = self.sample(&mut rng);
+                if rng.exhausted { None } else { Some(value) }
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl UniformInt<$ty> {
+            /// Sample `n` values, fetching the random words for them from the
+            /// RNG in bulk with a single call to `Rng::fill`. Extra words for
+            /// rejected values are taken from the RNG one at a time.
+            ///
+            /// Each word is used as if it was returned by `RngCore::next_u64`;
+            /// where a 32-bit value is needed, the low 32 bits of the word are
+            /// used.
+            pub fn sample_prefetched<R: Rng + ?Sized>(&self, rng: &mut R, n: usize)
+                -> Vec<$ty>
+            {
+                let words_per_sample = (::core::mem::size_of::<$u_large>() + 7) / 8;
+                let mut words = Vec::with_capacity(n * words_per_sample);
+                words.resize(n * words_per_sample, 0u64);
+                rng.fill(&mut words[..]);
+                let mut rng = PrefetchRng { words: words, pos: 0, rng: rng };
+                (0..n).map(|_| self.sample(&mut rng)).collect()
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl NoRepeat<$ty> {
+            /// Create a sampler from `distr` avoiding the last `k` values
+            /// returned. Panics if the range does not contain more than `k`
+            /// values.
+            pub fn new(distr: Uniform<$ty>, k: usize) -> NoRepeat<$ty> {
+                // A range of 0 represents the full range of the type.
+                let range = distr.inner.range as $unsigned;
+                assert!(k as $unsigned as usize == k &&
+                        (range == 0 || (k as $unsigned) < range),
+                        "NoRepeat::new called with `k` not smaller than the range");
+                NoRepeat {
+                    sampler: distr.inner,
+                    recent: Vec::with_capacity(k),
+                    k: k,
+                    next: 0,
+                }
+            }
+        }
+
+        impl Uniform<$ty> {
+            /// Return `preferred` with probability `p`, and otherwise sample a
+            /// value uniformly, e.g. to simulate users who usually keep a default
+            /// setting.
+            ///
+            /// Panics if `p` is not in `[0, 1]`, or if `preferred` is not in
+            /// the range.
+            pub fn sample_biased<R: Rng + ?Sized>(&self, rng: &mut R, preferred: $ty, p: f64)
+                -> $ty
+            {
+                assert!(0.0 <= p && p <= 1.0,
+                        "Uniform::sample_biased called with `p` outside [0, 1]");
+                assert!(self.inner.contains(preferred),
+                        "Uniform::sample_biased called with `preferred` outside the range");
+                if rng.gen_bool(p) { preferred } else { self.inner.sample(rng) }
+            }
+
+            /// Take `samples` samples, incrementing the bin of `bins` each
+            /// falls into (see [`bucket_of`]). This allows building a
+            /// histogram of many samples without storing them.
+            ///
+            /// Panics if `bins` is empty.
+            ///
+            /// [`bucket_of`]: struct.UniformInt.html#method.bucket_of
+            pub fn accumulate_histogram<R: Rng + ?Sized>(&self, rng: &mut R, bins: &mut [u64],
+                                                         samples: usize)
+            {
+                assert!(!bins.is_empty(),
+                        "Uniform::accumulate_histogram called with no bins");
+                for _ in 0..samples {
+                    let x = self.inner.sample(rng);
+                    bins[self.inner.bucket_of(x, bins.len())] += 1;
+                }
+            }
+
+            /// Create a new `Uniform` instance from a pair of `Bound`s, where an
+            /// `Unbounded` end extends to the limit of the type.
+            ///
+            /// Panics if the range is empty.
+            pub fn from_bounds(low: Bound<$ty>, high: Bound<$ty>) -> Uniform<$ty> {
+                let low = match low {
+                    Bound::Included(x) => x,
+                    Bound::Excluded(x) => {
+                        assert!(x < <$ty>::max_value(),
+                                "Uniform::from_bounds called with an empty range");
+                        x + 1
+                    }
+                    Bound::Unbounded => <$ty>::min_value(),
+                };
+                let high = match high {
+                    Bound::Included(x) => x,
+                    Bound::Excluded(x) => {
+                        assert!(x > <$ty>::min_value(),
+                                "Uniform::from_bounds called with an empty range");
+                        x - 1
+                    }
+                    Bound::Unbounded => <$ty>::max_value(),
+                };
+                assert!(low <= high, "Uniform::from_bounds called with an empty range");
+                Uniform::new_inclusive(low, high)
+            }
+
+            /// Create a new `Uniform` instance which samples uniformly from the
+            /// closed range `[center - half_width, center + half_width]`.
+            ///
+            /// The range may reach the limits of the type, e.g.
+            /// `new_centered(0i8, 127)` samples `[-127, 127]`.
+            ///
+            /// Panics if `half_width` is negative, or if `center - half_width`
+            /// or `center + half_width` does not fit in the type.
+            pub fn new_centered(center: $ty, half_width: $ty) -> Uniform<$ty> {
+                let (low, high) = match (center.checked_sub(half_width),
+                                         center.checked_add(half_width)) {
+                    (Some(low), Some(high)) => (low, high),
+                    _ => panic!("Uniform::new_centered called with a range \
+                                 beyond the limits of the type"),
+                };
+                assert!(low <= high,
+                        "Uniform::new_centered called with `half_width < 0`");
+                Uniform::new_inclusive(low, high)
+            }
+
+            /// Sample `k` distinct values from the range, i.e. sample without
+            /// replacement. The values are returned in the order they were
+            /// sampled.
+            ///
+            /// When `k` is small compared to the size of the range, values are
+            /// sampled repeatedly and duplicates are rejected. Otherwise all
+            /// values in the range are collected and a partial Fisher-Yates
+            /// shuffle is used.
+            ///
+            /// Panics if `k` is larger than the number of values in the range.
+            #[cfg(feature = "alloc")]
+            pub fn sample_distinct<R: Rng + ?Sized>(&self, rng: &mut R, k: usize)
+                -> Vec<$ty>
+            {
+                if k == 0 {
+                    return Vec::new();
+                }
+                // A range of 0 represents the full range of the type. If
+                // `k - 1` does not fit in `$unsigned`, `k` is larger than any
+                // range of the type.
+                let range = self.inner.range as $unsigned;
+                let last = k - 1;
+                assert!(last as $unsigned as usize == last &&
+                        (range == 0 || (last as $unsigned) < range),
+                        "Uniform::sample_distinct called with `k` larger than the range");
+
+                // The number of values in the range, if it fits in a `usize`.
+                let n = if range == 0 {
+                    let max = <$unsigned>::max_value();
+                    if max as usize as $unsigned == max {
+                        (max as usize).checked_add(1)
+                    } else {
+                        None
+                    }
+                } else if range as usize as $unsigned == range {
+                    Some(range as usize)
+                } else {
+                    None
+                };
+
+                match n {
+                    Some(n) if n / 2 <= k => {
+                        // Dense: `n <= 2 * k + 1`.
+                        let mut pool: Vec<$ty> = (0..n)
+                            .map(|i| self.inner.low.wrapping_add(i as $ty))
+                            .collect();
+                        for i in 0..k {
+                            let j = rng.gen_range(i, n);
+                            pool.swap(i, j);
+                        }
+                        pool.truncate(k);
+                        pool
+                    }
+                    _ => {
+                        #[cfg(feature = "std")]
+                        let mut seen = HashSet::with_capacity(k);
+                        #[cfg(not(feature = "std"))]
+                        let mut seen = BTreeSet::new();
+                        let mut result = Vec::with_capacity(k);
+                        while result.len() < k {
+                            let v = self.inner.sample(rng);
+                            if seen.insert(v) {
+                                result.push(v);
+                            }
+                        }
+                        result
+                    }
+                }
+            }
+        }
+    }
+}
+
+uniform_int_impl! { i8, i8, u8, i32, u32 }
+uniform_int_impl! { i16, i16, u16, i32, u32 }
+uniform_int_impl! { i32, i32, u32, i32, u32 }
+uniform_int_impl! { i64, i64, u64, i64, u64 }
+#[cfg(feature = "i128_support")]
+uniform_int_impl! { i128, i128, u128, u128, u128 }
+uniform_int_impl! { isize, isize, usize, isize, usize }
+uniform_int_impl! { u8, i8, u8, i32, u32 }
+uniform_int_impl! { u16, i16, u16, i32, u32 }
+uniform_int_impl! { u32, i32, u32, i32, u32 }
+uniform_int_impl! { u64, i64, u64, i64, u64 }
+uniform_int_impl! { usize, isize, usize, isize, usize }
+#[cfg(feature = "i128_support")]
+uniform_int_impl! { u128, u128, u128, i128, u128 }
+
+impl Uniform<i32> {
+    /// Create a new `Uniform` instance for fixed-point numbers with
+    /// `frac_bits` fractional bits (Q format), sampling the raw integers `x`
+    /// for which `x / 2^frac_bits` is in `[low, high)`.
+    ///
+    /// Only the bounds are converted from floating point; sampling happens in
+    /// integer space. Panics if `frac_bits >= 32`, if a bound does not fit in
+    /// the fixed-point format, or if the range contains no fixed-point values.
+    pub fn new_fixed(low: f64, high: f64, frac_bits: u32) -> Uniform<i32> {
+        assert!(frac_bits < 32, "Uniform::new_fixed called with `frac_bits >= 32`");
+        let scale = (1u64 << frac_bits) as f64;
+        // `ceil` is not available without `std`; this is equivalent for
+        // values which fit in an `i64`.
+        let ceil = |x: f64| {
+            let t = x as i64;
+            if (t as f64) < x { t + 1 } else { t }
+        };
+        let (low, high) = (low * scale, high * scale);
+        let min = ::core::i32::MIN as f64;
+        let max = ::core::i32::MAX as f64 + 1.0;
+        assert!(min <= low && high <= max,
+                "Uniform::new_fixed called with bounds outside the fixed-point range");
+        let (low, high) = (ceil(low), ceil(high) - 1);
+        assert!(low <= high, "Uniform::new_fixed called with an empty range");
+        Uniform::new_inclusive(low as i32, high as i32)
+    }
+}
+
+impl UniformInt<u8> {
+    /// Sample four values, using the four bytes of a single `u32` where
+    /// possible.
+    ///
+    /// Each byte is accepted or rejected on its own, in the same way `sample`
+    /// treats a full word; only the values for rejected bytes are sampled
+    /// again individually. For byte-heavy workloads this uses close to a
+    /// quarter of the random numbers [`sample`] would.
+    ///
+    /// [`sample`]: trait.UniformSampler.html#tymethod.sample
+    pub fn sample4<R: Rng + ?Sized>(&self, rng: &mut R) -> [u8; 4] {
+        let v: u32 = rng.gen();
+        let mut bytes = [v as u8, (v >> 8) as u8, (v >> 16) as u8, (v >> 24) as u8];
+        if self.range == 0 {
+            return bytes;
+        }
+        for b in bytes.iter_mut() {
+            let (hi, lo) = b.wmul(self.range);
+            *b = if lo <= self.zone {
+                self.low.wrapping_add(hi)
+            } else {
+                self.sample(rng)
+            };
+        }
+        bytes
+    }
+}
+
+// Methods only available for the signed integer types.
+macro_rules! uniform_int_signed_impl {
+    ($ty:ident, $unsigned:ident) => {
+        impl UniformInt<$ty> {
+            /// Sample a value and split it into whether it is negative and
+            /// its absolute value.
+            ///
+            /// The magnitude of `MIN` does not fit in the type; it saturates
+            /// to `MAX`, so `(true, MAX)` is returned for both `MIN` and
+            /// `-MAX`.
+            pub fn sample_signed_magnitude<R: Rng + ?Sized>(&self, rng: &mut R) -> (bool, $ty) {
+                let x = self.sample(rng);
+                (x < 0, x.checked_abs().unwrap_or(::core::$ty::MAX))
+            }
+        }
+
+        impl Uniform<$ty> {
+            /// Create a new `Uniform` instance which samples uniformly from the
+            /// half-open range `[-n, n)`, the same as `Uniform::new(-n, n)`.
+            /// Panics if `n <= 0`.
+            ///
+            /// The size of the range is calculated with a shift instead of a
+            /// wrapping subtraction and addition.
+            #[inline]
+            pub fn new_symmetric(n: $ty) -> Uniform<$ty> {
+                assert!(n > 0, "Uniform::new_symmetric called with `n <= 0`");
+                let unsigned_max = ::core::$unsigned::MAX;
+                let range = (n as $unsigned) << 1;
+                let ints_to_reject = (unsigned_max - range + 1) % range;
+                let zone = unsigned_max - ints_to_reject;
+                Uniform {
+                    inner: UniformInt {
+                        low: -n,
+                        range: range as $ty,
+                        zone: zone as $ty,
+                    }
+                }
+            }
+        }
+    }
+}
+
+uniform_int_signed_impl! { i8, u8 }
+uniform_int_signed_impl! { i16, u16 }
+uniform_int_signed_impl! { i32, u32 }
+uniform_int_signed_impl! { i64, u64 }
+#[cfg(feature = "i128_support")]
+uniform_int_signed_impl! { i128, u128 }
+uniform_int_signed_impl! { isize, usize }
+
+
+
+/// The back-end implementing [`UniformSampler`] for floating-point types.
+///
+/// Unless you are implementing [`UniformSampler`] for your own type, this type
+/// should not be used directly, use [`Uniform`] instead.
+///
+/// # Implementation notes
+///
+/// Instead of generating a float in the `[0, 1)` range using [`Standard`], the
+/// `UniformFloat` implementation converts the output of an PRNG itself. This
+/// way one or two steps can be optimized out.
+///
+/// The floats are first converted to a value in the `[1, 2)` interval using a
 /// transmute-based method, and then mapped to the expected range with a
 /// multiply and addition. Values produced this way have what equals 22 bits of
 /// random digits for an `f32`, and 52 for an `f64`.
@@ -491,15 +1674,36 @@ uniform_int_impl! { u128, u128, u128, i128, u128 }
 /// because the boundaries of a floats range are a bit of a fuzzy concept due to
 /// rounding errors.
 ///
+/// Ranges within `[-2 * MIN_POSITIVE, 2 * MIN_POSITIVE]` (subnormals and the
+/// smallest normal exponent) can be so narrow that the multiply and addition
+/// round to only a few values, and not uniformly. [`UniformUlps`] samples
+/// such ranges exactly.
+///
+/// [`new_with_bits`] constructs a [`UniformFloatBits`] sampler using fewer
+/// random mantissa bits, producing values on a coarser grid.
+///
+/// A closed range with `low == high` always returns `low`, without using the
+/// RNG.
+///
+/// Negative zero is never sampled, also not for ranges spanning or bounded by
+/// zero: the product in the multiply and addition is positive, so the sum is
+/// only zero as `+0.0`. This means samples can be serialized without having to
+/// normalize the sign of zero.
+///
 /// [`UniformSampler`]: trait.UniformSampler.html
+/// [`UniformUlps`]: struct.UniformUlps.html
 /// [`new`]: trait.UniformSampler.html#tymethod.new
 /// [`new_inclusive`]: trait.UniformSampler.html#tymethod.new_inclusive
+/// [`new_with_bits`]: #method.new_with_bits
+/// [`UniformFloatBits`]: struct.UniformFloatBits.html
 /// [`Uniform`]: struct.Uniform.html
 /// [`Standard`]: ../struct.Standard.html
 #[derive(Clone, Copy, Debug)]
 pub struct UniformFloat<X> {
     scale: X,
     offset: X,
+    low: X,
+    high: X,
 }
 
 macro_rules! uniform_float_impl {
@@ -524,6 +1728,8 @@ macro_rules! uniform_float_impl {
                 UniformFloat {
                     scale: scale,
                     offset: offset,
+                    low: low,
+                    high: high,
                 }
             }
 
@@ -540,10 +1746,15 @@ macro_rules! uniform_float_impl {
                 UniformFloat {
                     scale: scale,
                     offset: offset,
+                    low: low,
+                    high: high,
                 }
             }
 
             fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+                if self.high.all_le(self.low) {
+                    return self.sample_degenerate();
+                }
                 // Generate a value in the range [1, 2)
                 let value: $uty = rng.gen::<$uty>() >> $bits_to_discard;
                 let value1_2 = value.into_float_with_exponent(0);
@@ -574,6 +1785,17 @@ macro_rules! uniform_float_impl {
                 value1_2 * scale + offset
             }
         }
+
+        impl UniformFloat<$ty> {
+            /// The sample of a closed range with `low == high`: there is
+            /// nothing to draw. Adding `scale`, which is zero, turns `-0.0`
+            /// into `0.0`.
+            #[cold]
+            #[inline(never)]
+            fn sample_degenerate(&self) -> $ty {
+                self.high + self.scale
+            }
+        }
     }
 }
 
@@ -596,300 +1818,4714 @@ uniform_float_impl! { f64x4, u64x4, 64 - 52 }
 #[cfg(feature="simd_support")]
 uniform_float_impl! { f64x8, u64x8, 64 - 52 }
 
-
-
-/// The back-end implementing [`UniformSampler`] for `Duration`.
+/// A distribution sampling floats in ranges within
+/// `[-2 * MIN_POSITIVE, 2 * MIN_POSITIVE]` exactly, available for `f32` and
+/// `f64`.
 ///
-/// Unless you are implementing [`UniformSampler`] for your own types, this type
-/// should not be used directly, use [`Uniform`] instead.
+/// In this region (subnormals and the smallest normal exponent) floats are
+/// evenly spaced, so an integer number of ULPs (units in the last place) is
+/// sampled using [`UniformInt`] and converted to a float. Unlike
+/// [`UniformFloat`], which may round to only a few values in such narrow
+/// ranges, every float in the range is equally likely, and [`new`] never
+/// returns `high`. A ULP count of zero converts to `+0.0`, so negative zero is
+/// never sampled.
 ///
-/// [`UniformSampler`]: trait.UniformSampler.html
-/// [`Uniform`]: struct.Uniform.html
-#[cfg(feature = "std")]
+/// [`UniformInt`]: struct.UniformInt.html
+/// [`UniformFloat`]: struct.UniformFloat.html
+/// [`new`]: #method.new
 #[derive(Clone, Copy, Debug)]
-pub struct UniformDuration {
-    offset: Duration,
-    mode: UniformDurationMode,
+pub struct UniformUlps<X> {
+    ulps: UniformInt<i64>,
+    phantom: ::core::marker::PhantomData<X>,
 }
 
-#[cfg(feature = "std")]
-#[derive(Debug, Copy, Clone)]
-enum UniformDurationMode {
-    Small {
-        nanos: Uniform<u64>,
-    },
-    Large {
-        size: Duration,
-        secs: Uniform<u64>,
-    }
+/// A distribution sampling floats uniformly from `[low, high)` using fewer
+/// random mantissa bits than [`UniformFloat`], so that samples lie on a grid
+/// of evenly spaced values. Available for `f32` and `f64`.
+///
+/// This `struct` is created by [`UniformFloat::new_with_bits`]. Note that it
+/// is not faster than [`UniformFloat`]: one random word is still used per
+/// sample.
+///
+/// [`UniformFloat`]: struct.UniformFloat.html
+/// [`UniformFloat::new_with_bits`]: struct.UniformFloat.html#method.new_with_bits
+#[derive(Clone, Copy, Debug)]
+pub struct UniformFloatBits<X> {
+    inner: UniformFloat<X>,
+    /// The number of low mantissa bits to clear.
+    shift: u32,
 }
 
-#[cfg(feature = "std")]
-impl SampleUniform for Duration {
-    type Sampler = UniformDuration;
-}
+// Methods only available for the scalar float types.
+macro_rules! uniform_float_scalar_impl {
+    ($ty:ty, $uty:ident, $bits_to_discard:expr) => {
+        impl Distribution<$ty> for UniformFloatBits<$ty> {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                // Generate a value in the range [1, 2), with the lowest
+                // `shift` mantissa bits cleared
+                let value: $uty = rng.gen::<$uty>() >> $bits_to_discard;
+                let value = value >> self.shift << self.shift;
+                let value1_2 = value.into_float_with_exponent(0);
+                value1_2 * self.inner.scale + self.inner.offset
+            }
+        }
 
-#[cfg(feature = "std")]
-impl UniformSampler for UniformDuration {
-    type X = Duration;
+        impl UniformUlps<$ty> {
+            /// Create a sampler for `[low, high)`.
+            ///
+            /// Panics if `low >= high`, or if the range is not within
+            /// `[-2 * MIN_POSITIVE, 2 * MIN_POSITIVE]`.
+            pub fn new(low: $ty, high: $ty) -> UniformUlps<$ty> {
+                assert!(low < high, "UniformUlps::new called with `low >= high`");
+                assert!(<$ty>::evenly_spaced(low, high),
+                        "UniformUlps::new called with a range that is not evenly spaced");
+                UniformUlps {
+                    ulps: UniformInt::<i64>::new(low.to_ordered_int(), high.to_ordered_int()),
+                    phantom: ::core::marker::PhantomData,
+                }
+            }
 
-    #[inline]
-    fn new<B1, B2>(low_b: B1, high_b: B2) -> Self
-        where B1: SampleBorrow<Self::X> + Sized,
-              B2: SampleBorrow<Self::X> + Sized
-    {
-        let low = *low_b.borrow();
-        let high = *high_b.borrow();
-        assert!(low < high, "Uniform::new called with `low >= high`");
-        UniformDuration::new_inclusive(low, high - Duration::new(0, 1))
-    }
+            /// Create a sampler for `[low, high]`.
+            ///
+            /// Panics if `low > high`, or if the range is not within
+            /// `[-2 * MIN_POSITIVE, 2 * MIN_POSITIVE]`.
+            pub fn new_inclusive(low: $ty, high: $ty) -> UniformUlps<$ty> {
+                assert!(low <= high, "UniformUlps::new_inclusive called with `low > high`");
+                assert!(<$ty>::evenly_spaced(low, high),
+                        "UniformUlps::new_inclusive called with a range that is not evenly spaced");
+                UniformUlps {
+                    ulps: UniformInt::<i64>::new_inclusive(low.to_ordered_int(),
+                                                           high.to_ordered_int()),
+                    phantom: ::core::marker::PhantomData,
+                }
+            }
+        }
 
-    #[inline]
-    fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
-        where B1: SampleBorrow<Self::X> + Sized,
-              B2: SampleBorrow<Self::X> + Sized
-    {
-        let low = *low_b.borrow();
-        let high = *high_b.borrow();
-        assert!(low <= high, "Uniform::new_inclusive called with `low > high`");
-        let size = high - low;
-        let nanos = size
-            .as_secs()
-            .checked_mul(1_000_000_000)
-            .and_then(|n| n.checked_add(size.subsec_nanos() as u64));
+        impl Distribution<$ty> for UniformUlps<$ty> {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                <$ty>::from_ordered_int(self.ulps.sample(rng))
+            }
+        }
 
-        let mode = match nanos {
-            Some(nanos) => {
-                UniformDurationMode::Small {
-                    nanos: Uniform::new_inclusive(0, nanos),
+        impl UniformFloat<$ty> {
+            /// Create a sampler for `[low, high)` using only `mantissa_bits`
+            /// random bits for each sample, instead of the full 23 for `f32`
+            /// or 52 for `f64`. Samples are then on a grid of at most
+            /// `2^mantissa_bits` evenly spaced values.
+            ///
+            /// Panics if `low >= high` or if `mantissa_bits` is larger than
+            /// the mantissa of the type.
+            pub fn new_with_bits(low: $ty, high: $ty, mantissa_bits: u32) -> UniformFloatBits<$ty> {
+                let max_bits = (::core::mem::size_of::<$ty>() * 8) as u32 - $bits_to_discard;
+                assert!(mantissa_bits <= max_bits,
+                        "UniformFloat::new_with_bits called with `mantissa_bits` too large");
+                UniformFloatBits {
+                    inner: <UniformFloat<$ty> as UniformSampler>::new(low, high),
+                    shift: max_bits - mantissa_bits,
                 }
             }
-            None => {
-                UniformDurationMode::Large {
-                    size: size,
-                    secs: Uniform::new_inclusive(0, size.as_secs()),
+
+            /// Mean of `n` samples, accumulated with Kahan summation so that
+            /// rounding errors do not grow with `n`.
+            #[cfg(test)]
+            fn sample_mean<R: Rng + ?Sized>(&self, rng: &mut R, n: usize) -> f64 {
+                let mut sum = 0.0f64;
+                let mut compensation = 0.0f64;
+                for _ in 0..n {
+                    let y = self.sample(rng) as f64 - compensation;
+                    let t = sum + y;
+                    compensation = (t - sum) - y;
+                    sum = t;
                 }
+                sum / n as f64
             }
-        };
 
-        UniformDuration {
-            mode,
-            offset: low,
-        }
-    }
+            /// Sample a value, and report whether it is exactly equal to the
+            /// `high` bound the sampler was constructed with.
+            ///
+            /// Because of rounding, [`new`] may occasionally return `high`
+            /// although the range is meant to be half-open; this allows
+            /// detecting that case.
+            ///
+            /// [`new`]: trait.UniformSampler.html#tymethod.new
+            pub fn sample_checked<R: Rng + ?Sized>(&self, rng: &mut R) -> ($ty, bool) {
+                let x = self.sample(rng);
+                (x, x == self.high)
+            }
+
+            /// Sample a value, also returning the random bits it was
+            /// constructed from, for debugging and auditing.
+            ///
+            /// These are the mantissa bits of the intermediate value in
+            /// `[1, 2)` (23 bits for `f32`, 52 for `f64`).
+            pub fn sample_raw<R: Rng + ?Sized>(&self, rng: &mut R) -> ($ty, u64) {
+                let value: $uty = rng.gen::<$uty>() >> $bits_to_discard;
+                let value1_2 = value.into_float_with_exponent(0);
+                (value1_2 * self.scale + self.offset, value as u64)
+            }
+
+            /// The number of bytes a sample takes from the RNG: a sample
+            /// needs exactly one draw, except for a closed range with
+            /// `low == high` which does not use the RNG at all.
+            pub fn max_bytes_per_sample(&self) -> usize {
+                if self.high <= self.low {
+                    return 0;
+                }
+                ::core::mem::size_of::<$uty>()
+            }
+
+            /// The expected number of bytes a sample takes from the RNG. As
+            /// no draws are rejected, this equals [`max_bytes_per_sample`].
+            ///
+            /// [`max_bytes_per_sample`]: #method.max_bytes_per_sample
+            pub fn expected_bytes_per_sample(&self) -> f64 {
+                self.max_bytes_per_sample() as f64
+            }
+
+            /// Sample a value, also returning the uniform `q` in `[0, 1)` it was
+            /// constructed from, such that the value is `low + q * (high - low)`
+            /// up to rounding, e.g. to compute weights for importance
+            /// sampling. See also [`quantile`].
+            ///
+            /// [`quantile`]: #method.quantile
+            pub fn sample_with_uniform<R: Rng + ?Sized>(&self, rng: &mut R) -> ($ty, f64) {
+                if self.high <= self.low {
+                    return (self.sample_degenerate(), 0.0);
+                }
+                let value: $uty = rng.gen::<$uty>() >> $bits_to_discard;
+                let value1_2 = value.into_float_with_exponent(0);
+                (value1_2 * self.scale + self.offset, (value1_2 - 1.0) as f64)
+            }
+
+            /// The value at quantile `q` of the distribution, i.e. the inverse
+            /// of its cumulative distribution function: `low + q * (high - low)`.
+            /// Panics if `q` is not in `[0, 1)`.
+            ///
+            /// Together with a source of well-spread quantiles, e.g. a
+            /// low-discrepancy sequence, this can be used for stratified
+            /// sampling.
+            pub fn quantile(&self, q: f64) -> $ty {
+                assert!(0.0 <= q && q < 1.0,
+                        "UniformFloat::quantile called with `q` outside [0, 1)");
+                self.low + q as $ty * (self.high - self.low)
+            }
+
+            /// The cumulative distribution function: the probability that a
+            /// sample is at most `x`, i.e. `(x - low) / (high - low)` clamped
+            /// to `[0, 1]`.
+            ///
+            /// This is the inverse of [`quantile`], and can e.g. be used to
+            /// test a sampler via the probability integral transform.
+            ///
+            /// [`quantile`]: #method.quantile
+            pub fn cdf(&self, x: $ty) -> f64 {
+                // Check `high` first: for a closed range with `low == high`,
+                // all samples are at most `low`.
+                if x >= self.high {
+                    1.0
+                } else if x <= self.low {
+                    0.0
+                } else {
+                    ((x - self.low) / (self.high - self.low)) as f64
+                }
+            }
+
+            /// The probability density inside the range, `1 / (high - low)`,
+            /// e.g. for computing acceptance ratios when using this
+            /// distribution as a proposal. This is infinite for a closed range
+            /// with `low == high`.
+            pub fn pdf(&self) -> f64 {
+                // Subtract as `f64`: for `f32` the width may overflow.
+                1.0 / (self.high as f64 - self.low as f64)
+            }
+
+            /// The index of the bin `x` falls into when the range is divided
+            /// into `bins` bins of equal width, e.g. for building a histogram.
+            /// Values outside the range are put in the first or last bin.
+            /// Panics if `bins == 0`.
+            pub fn bucket_of(&self, x: $ty, bins: usize) -> usize {
+                assert!(bins > 0, "UniformFloat::bucket_of called with `bins == 0`");
+                let bin = self.cdf(x) * bins as f64;
+                if bin < bins as f64 { bin as usize } else { bins - 1 }
+            }
+
+            /// Map `x` into `[low, high]` by mirror reflection at the bounds,
+            /// e.g. to keep a random walk inside the range: `high + d` maps to
+            /// `high - d`, and `low - d` to `low + d`. Values further away are
+            /// folded repeatedly, like a triangle wave.
+            pub fn reflect(&self, x: $ty) -> $ty {
+                let width = self.high - self.low;
+                if width == 0.0 {
+                    return self.low;
+                }
+                let d = x - self.low;
+                let d = if d < 0.0 { -d } else { d };
+                let mut t = d % (2.0 * width);
+                if t > width {
+                    t = 2.0 * width - t;
+                }
+                let r = self.low + t;
+                // Guard against rounding.
+                if r > self.high { self.high } else { r }
+            }
+
+            /// Sample a value biased towards `high`: the larger of two
+            /// samples. Scaled to `[0, 1)`, this has the Beta(2, 1)
+            /// distribution, with a density increasing linearly from zero and
+            /// a mean at two thirds of the range.
+            ///
+            /// This is a cheap alternative to a full Beta distribution.
+            pub fn sample_biased_high<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                let a = self.sample(rng);
+                let b = self.sample(rng);
+                if a > b { a } else { b }
+            }
+
+            /// Sample a value biased towards `low`: the smaller of two
+            /// samples. Scaled to `[0, 1)`, this has the Beta(1, 2)
+            /// distribution, with a density decreasing linearly to zero and a
+            /// mean at one third of the range.
+            pub fn sample_biased_low<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                let a = self.sample(rng);
+                let b = self.sample(rng);
+                if a < b { a } else { b }
+            }
+
+            /// Sample the smallest of `n` independent samples, e.g. to model
+            /// the fastest of `n` latencies, using a single uniform draw.
+            ///
+            /// The minimum of `n` uniform samples from `[0, 1)` has the
+            /// distribution of `1 - (1 - u)^(1/n)` for uniform `u`, so this is
+            /// exact up to floating-point rounding; the result is then mapped
+            /// to the range with [`quantile`]. Panics if `n == 0`.
+            ///
+            /// [`quantile`]: #method.quantile
+            #[cfg(feature = "std")]
+            pub fn sample_min_of<R: Rng + ?Sized>(&self, rng: &mut R, n: u32) -> $ty {
+                assert!(n > 0, "UniformFloat::sample_min_of called with `n == 0`");
+                let u = UniformFloat::<f64>::sample_single(0.0, 1.0, rng);
+                self.quantile(1.0 - (1.0 - u).powf(1.0 / n as f64))
+            }
+
+            /// Sample the largest of `n` independent samples, e.g. to model
+            /// the slowest of `n` latencies, using a single uniform draw.
+            ///
+            /// The maximum of `n` uniform samples from `[0, 1)` has the
+            /// distribution of `u^(1/n)` for uniform `u`, so as for
+            /// [`sample_min_of`] this is exact up to floating-point rounding.
+            /// Panics if `n == 0`.
+            ///
+            /// [`sample_min_of`]: #method.sample_min_of
+            #[cfg(feature = "std")]
+            pub fn sample_max_of<R: Rng + ?Sized>(&self, rng: &mut R, n: u32) -> $ty {
+                assert!(n > 0, "UniformFloat::sample_max_of called with `n == 0`");
+                let u = UniformFloat::<f64>::sample_single(0.0, 1.0, rng);
+                let q = u.powf(1.0 / n as f64);
+                // For large `n` the root may round up to 1.
+                let q = if q < 1.0 { q } else { 1.0 - ::core::f64::EPSILON / 2.0 };
+                self.quantile(q)
+            }
+
+            /// Sample the difference of two independent samples, e.g. to model
+            /// a random increment.
+            ///
+            /// With `w = high - low`, the difference lies in `(-w, w)` and has
+            /// a triangular probability density `(w - |d|) / w²`, peaking at
+            /// zero, so the mean is zero.
+            pub fn sample_diff<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                let a = self.sample(rng);
+                let b = self.sample(rng);
+                a - b
+            }
+
+            /// Fill a buffer of possibly uninitialized memory with samples,
+            /// avoiding the cost of initializing it first, e.g. for
+            /// multi-megabyte buffers.
+            ///
+            /// Every element of `dst` is written, so once this returns the
+            /// caller may soundly call `assume_init` on all of them. Values
+            /// previously in `dst` are overwritten without being dropped.
+            #[cfg(feature = "maybe_uninit_support")]
+            pub fn sample_into_uninit<R: Rng + ?Sized>(&self, rng: &mut R,
+                                                       dst: &mut [MaybeUninit<$ty>])
+            {
+                for x in dst.iter_mut() {
+                    *x = MaybeUninit::new(self.sample(rng));
+                }
+            }
+
+            /// Sample a value biased by an easing function, e.g. for
+            /// animation: a uniform `q` in `[0, 1)` is passed through `ease`,
+            /// and the result is mapped to the range with [`quantile`].
+            ///
+            /// `ease` must map `[0, 1)` into `[0, 1)`; this panics otherwise.
+            /// For example `|q| q * q` (ease-in) clusters samples towards
+            /// `low`, and `|q| 1.0 - (1.0 - q) * (1.0 - q)` towards `high`.
+            ///
+            /// [`quantile`]: #method.quantile
+            pub fn sample_eased<R: Rng + ?Sized>(&self, rng: &mut R, ease: fn(f64) -> f64)
+                -> $ty
+            {
+                let q = UniformFloat::<f64>::sample_single(0.0, 1.0, rng);
+                self.quantile(ease(q))
+            }
+
+            /// Sample a value conditioned on also lying in `[a, b)`.
+            ///
+            /// Since the distribution is uniform, this is equivalent to
+            /// sampling uniformly from the intersection of the two ranges, so
+            /// no samples are rejected. Panics if `[a, b)` does not overlap
+            /// the range of this distribution.
+            pub fn sample_conditioned<R: Rng + ?Sized>(&self, rng: &mut R, a: $ty, b: $ty)
+                -> $ty
+            {
+                let low = if a > self.low { a } else { self.low };
+                let high = if b < self.high { b } else { self.high };
+                assert!(low < high,
+                        "UniformFloat::sample_conditioned called with a window outside the range");
+                UniformFloat::<$ty>::sample_single(low, high, rng)
+            }
+
+            /// Sample a value near `center`, uniformly from the window
+            /// `[center - spread, center + spread)` intersected with the range
+            /// of this distribution, e.g. for agents moving around within
+            /// bounds.
+            ///
+            /// Panics if `spread` is not positive or if the window lies
+            /// entirely outside the range.
+            pub fn sample_near<R: Rng + ?Sized>(&self, rng: &mut R, center: $ty, spread: $ty)
+                -> $ty
+            {
+                assert!(spread > 0.0, "UniformFloat::sample_near called with `spread <= 0`");
+                self.sample_conditioned(rng, center - spread, center + spread)
+            }
+
+            /// Sample a value rounded to `decimals` decimal places, e.g. for
+            /// human-readable output.
+            ///
+            /// The result stays within `[low, high]`: if rounding to the
+            /// nearest value would leave the range, the value is rounded in the
+            /// other direction, and if the range contains no such value at
+            /// all, it is clamped. Note that most decimal fractions are not
+            /// exactly representable; the result is the nearest float.
+            #[cfg(feature = "std")]
+            pub fn sample_rounded_decimals<R: Rng + ?Sized>(&self, rng: &mut R, decimals: u32)
+                -> $ty
+            {
+                let y = self.sample(rng);
+                // Capping `decimals` keeps the cast from wrapping; `p` is
+                // infinite either way.
+                let p = (10.0 as $ty).powi(::core::cmp::min(decimals, 1000) as i32);
+                let x = y * p;
+                if !x.is_finite() {
+                    // With this many decimals `p`, or the scaled value,
+                    // overflows; the value is then already as precise as
+                    // rounding could make it.
+                    return y;
+                }
+                let mut r = x.round() / p;
+                if r < self.low {
+                    r = x.ceil() / p;
+                } else if r > self.high {
+                    r = x.floor() / p;
+                }
+                if r < self.low {
+                    self.low
+                } else if r > self.high {
+                    self.high
+                } else {
+                    r
+                }
+            }
+
+            /// Fill `out` with one sample from each of `strata` equally sized
+            /// sub-ranges, in order. Panics if `out.len() != strata`.
+            ///
+            /// This covers the range more evenly than independent samples,
+            /// reducing the variance of e.g. Monte Carlo estimates. The values
+            /// are not independent, and are in non-decreasing order.
+            pub fn sample_stratified<R: Rng + ?Sized>(&self, rng: &mut R, strata: usize,
+                                                      out: &mut [$ty])
+            {
+                assert_eq!(out.len(), strata,
+                           "UniformFloat::sample_stratified called with `out.len() != strata`");
+                let largest_below_one = 1.0 - ::core::f64::EPSILON / 2.0;
+                for (i, x) in out.iter_mut().enumerate() {
+                    let u: f64 = rng.gen();
+                    let q = (i as f64 + u) / strata as f64;
+                    *x = self.quantile(if q < 1.0 { q } else { largest_below_one });
+                }
+            }
+
+            /// Sample `n` values, returned in non-decreasing order.
+            ///
+            /// Instead of sorting `n` samples in `O(n log n)` time, the order
+            /// statistics are generated directly in `O(n)`: the partial sums of
+            /// `n + 1` exponentially distributed values, divided by their
+            /// total, are distributed as `n` sorted samples from `[0, 1)`.
+            ///
+            /// This method requires `std`, for the exponential distribution.
+            #[cfg(feature = "std")]
+            pub fn sample_sorted<R: Rng + ?Sized>(&self, rng: &mut R, n: usize)
+                -> Vec<$ty>
+            {
+                use distributions::Exp1;
+                let mut sums = Vec::with_capacity(n);
+                let mut sum = 0.0;
+                for _ in 0..n {
+                    sum += rng.sample::<f64, _>(Exp1);
+                    sums.push(sum);
+                }
+                let total = sum + rng.sample::<f64, _>(Exp1);
+                sums.into_iter()
+                    .map(|s| (1.0 + (s / total) as $ty) * self.scale + self.offset)
+                    .collect()
+            }
+
+            /// Sample a pair of values with a rank correlation of
+            /// approximately `rho`, which must be in `[0, 1]`.
+            ///
+            /// With probability `rho` the second value is a copy of the first,
+            /// otherwise it is sampled independently. Both values are uniformly
+            /// distributed; the rank correlation is `rho` in expectation, but
+            /// the joint distribution is far from e.g. a Gaussian copula.
+            pub fn sample_correlated<R: Rng + ?Sized>(&self, rng: &mut R, rho: f64)
+                -> ($ty, $ty)
+            {
+                assert!(0.0 <= rho && rho <= 1.0,
+                        "UniformFloat::sample_correlated called with `rho` outside [0, 1]");
+                let x = self.sample(rng);
+                if rng.gen_bool(rho) {
+                    (x, x)
+                } else {
+                    (x, self.sample(rng))
+                }
+            }
+        }
+
+        impl Uniform<$ty> {
+            /// Return `preferred` with probability `p`, and otherwise sample a
+            /// value uniformly, e.g. to simulate users who usually keep a default
+            /// setting.
+            ///
+            /// Panics if `p` is not in `[0, 1]`, or if `preferred` is not in
+            /// `[low, high]`.
+            pub fn sample_biased<R: Rng + ?Sized>(&self, rng: &mut R, preferred: $ty, p: f64)
+                -> $ty
+            {
+                assert!(0.0 <= p && p <= 1.0,
+                        "Uniform::sample_biased called with `p` outside [0, 1]");
+                assert!(self.inner.low <= preferred && preferred <= self.inner.high,
+                        "Uniform::sample_biased called with `preferred` outside the range");
+                if rng.gen_bool(p) { preferred } else { self.inner.sample(rng) }
+            }
+
+            /// Take `samples` samples, incrementing the bin of `bins` each
+            /// falls into (see [`bucket_of`]). This allows building a
+            /// histogram of many samples without storing them.
+            ///
+            /// Panics if `bins` is empty.
+            ///
+            /// [`bucket_of`]: struct.UniformFloat.html#method.bucket_of
+            pub fn accumulate_histogram<R: Rng + ?Sized>(&self, rng: &mut R, bins: &mut [u64],
+                                                         samples: usize)
+            {
+                assert!(!bins.is_empty(),
+                        "Uniform::accumulate_histogram called with no bins");
+                for _ in 0..samples {
+                    let x = self.inner.sample(rng);
+                    bins[self.inner.bucket_of(x, bins.len())] += 1;
+                }
+            }
+
+            /// Create a distribution sampling uniformly from `n` evenly spaced
+            /// levels in `[low, high]`: `low + k * (high - low) / (n - 1)` for
+            /// `k` in `[0, n)`, e.g. for quantized audio gain steps. Both
+            /// `low` and `high` are levels.
+            ///
+            /// Panics if `n < 2` or `low >= high`.
+            pub fn new_levels(low: $ty, high: $ty, n: usize) -> UniformLevels<$ty> {
+                assert!(n >= 2, "Uniform::new_levels called with `n < 2`");
+                assert!(low < high, "Uniform::new_levels called with `low >= high`");
+                UniformLevels {
+                    low: low,
+                    high: high,
+                    step: (high - low) / (n - 1) as $ty,
+                    index: UniformInt::<usize>::new(0, n),
+                }
+            }
+
+            /// Create a new `Uniform` instance which samples uniformly from
+            /// `[center - half_width, center + half_width)`.
+            ///
+            /// Panics if `half_width` is not positive.
+            pub fn new_centered(center: $ty, half_width: $ty) -> Uniform<$ty> {
+                assert!(half_width > 0.0,
+                        "Uniform::new_centered called with `half_width <= 0`");
+                Uniform::new(center - half_width, center + half_width)
+            }
+
+            /// Create a new `Uniform` instance from bounds given as percentages,
+            /// sampling fractions from `[low_pct / 100, high_pct / 100)`.
+            ///
+            /// Panics if the bounds are not within `[0, 100]`, or if
+            /// `low_pct >= high_pct`.
+            pub fn new_percent(low_pct: $ty, high_pct: $ty) -> Uniform<$ty> {
+                assert!(0.0 <= low_pct && high_pct <= 100.0,
+                        "Uniform::new_percent called with bounds outside [0, 100]");
+                Uniform::new(low_pct / 100.0, high_pct / 100.0)
+            }
+
+            /// Create a new `Uniform` instance from bounds given in degrees,
+            /// sampling the corresponding angles in radians from
+            /// `[low_deg * π / 180, high_deg * π / 180)`.
+            ///
+            /// The bounds are converted once here, so samples need no
+            /// conversion. Panics if `low_deg >= high_deg`.
+            pub fn new_degrees(low_deg: $ty, high_deg: $ty) -> Uniform<$ty> {
+                assert!(low_deg < high_deg,
+                        "Uniform::new_degrees called with `low_deg >= high_deg`");
+                let scale = ::core::f64::consts::PI as $ty / 180.0;
+                Uniform::new(low_deg * scale, high_deg * scale)
+            }
+        }
+    }
+}
+
+macro_rules! uniform_levels_impl {
+    ($ty:ty) => {
+        impl Distribution<$ty> for UniformLevels<$ty> {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                let k = self.index.sample(rng);
+                // Return the top level exactly, rather than with the rounding
+                // error of the multiplication.
+                if k == self.index.last() {
+                    self.high
+                } else {
+                    self.low + k as $ty * self.step
+                }
+            }
+        }
+    }
+}
+
+uniform_levels_impl! { f32 }
+uniform_levels_impl! { f64 }
+
+uniform_float_scalar_impl! { f32, u32, 32 - 23 }
+uniform_float_scalar_impl! { f64, u64, 64 - 52 }
+
+impl UniformFloat<f64> {
+    /// A sampler for a random phase angle in radians, in `[0, 2π)`.
+    ///
+    /// As for other half-open ranges, rounding may rarely produce exactly
+    /// `2π`, which is equivalent to a phase of `0`.
+    pub fn phase() -> UniformFloat<f64> {
+        UniformFloat::new(0.0, 2.0 * ::core::f64::consts::PI)
+    }
+}
+
+
+
+/// The back-end implementing [`UniformSampler`] for `char`.
+///
+/// Unless you are implementing [`UniformSampler`] for your own type, this type
+/// should not be used directly, use [`Uniform`] instead.
+///
+/// # Implementation notes
+///
+/// A valid `char` is any code point except the surrogates `[0xD800, 0xDFFF]`.
+/// Code points are mapped to a contiguous range of `u32` by closing this gap,
+/// sampled with [`UniformInt`], and mapped back.
+///
+/// [`UniformSampler`]: trait.UniformSampler.html
+/// [`Uniform`]: struct.Uniform.html
+/// [`UniformInt`]: struct.UniformInt.html
+#[derive(Clone, Copy, Debug)]
+pub struct UniformChar {
+    sampler: UniformInt<u32>,
+}
+
+const CHAR_SURROGATE_START: u32 = 0xD800;
+const CHAR_SURROGATE_LEN: u32 = 0xDFFF - 0xD800 + 1;
+
+/// Map a `char` to a `u32` such that the surrogate gap is closed.
+fn char_to_comp_u32(c: char) -> u32 {
+    let c = c as u32;
+    if c >= CHAR_SURROGATE_START { c - CHAR_SURROGATE_LEN } else { c }
+}
+
+impl SampleUniform for char {
+    type Sampler = UniformChar;
+}
+
+impl UniformSampler for UniformChar {
+    type X = char;
 
     #[inline]
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Duration {
-        let d = match self.mode {
-            UniformDurationMode::Small { nanos } => {
-                let nanos = nanos.sample(rng);
-                Duration::new(nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32)
+    fn new<B1, B2>(low_b: B1, high_b: B2) -> Self
+        where B1: SampleBorrow<Self::X> + Sized,
+              B2: SampleBorrow<Self::X> + Sized
+    {
+        let low = char_to_comp_u32(*low_b.borrow());
+        let high = char_to_comp_u32(*high_b.borrow());
+        UniformChar { sampler: UniformInt::<u32>::new(low, high) }
+    }
+
+    #[inline]
+    fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
+        where B1: SampleBorrow<Self::X> + Sized,
+              B2: SampleBorrow<Self::X> + Sized
+    {
+        let low = char_to_comp_u32(*low_b.borrow());
+        let high = char_to_comp_u32(*high_b.borrow());
+        UniformChar { sampler: UniformInt::<u32>::new_inclusive(low, high) }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        let mut x = self.sampler.sample(rng);
+        if x >= CHAR_SURROGATE_START {
+            x += CHAR_SURROGATE_LEN;
+        }
+        // Safe: `x` is a valid code point outside the surrogate range.
+        unsafe { ::core::char::from_u32_unchecked(x) }
+    }
+}
+
+/// A selection of Unicode blocks, for use with [`UniformChar::from_block`].
+///
+/// Blocks are contiguous ranges of code points; they may contain code points
+/// which are unassigned or are not printable characters.
+///
+/// [`UniformChar::from_block`]: struct.UniformChar.html#method.from_block
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnicodeBlock {
+    /// Basic Latin (ASCII), `U+0000` to `U+007F`.
+    BasicLatin,
+    /// Latin-1 Supplement, `U+0080` to `U+00FF`.
+    Latin1Supplement,
+    /// Greek and Coptic, `U+0370` to `U+03FF`.
+    Greek,
+    /// Cyrillic, `U+0400` to `U+04FF`.
+    Cyrillic,
+    /// Hebrew, `U+0590` to `U+05FF`.
+    Hebrew,
+    /// Arabic, `U+0600` to `U+06FF`.
+    Arabic,
+    /// Hiragana, `U+3040` to `U+309F`.
+    Hiragana,
+    /// Katakana, `U+30A0` to `U+30FF`.
+    Katakana,
+    /// CJK Unified Ideographs, `U+4E00` to `U+9FFF`.
+    CjkUnifiedIdeographs,
+    /// Hangul Syllables, `U+AC00` to `U+D7AF`.
+    HangulSyllables,
+}
+
+impl UnicodeBlock {
+    /// The first and last code point of the block.
+    pub fn bounds(self) -> (char, char) {
+        match self {
+            UnicodeBlock::BasicLatin => ('\u{0000}', '\u{007F}'),
+            UnicodeBlock::Latin1Supplement => ('\u{0080}', '\u{00FF}'),
+            UnicodeBlock::Greek => ('\u{0370}', '\u{03FF}'),
+            UnicodeBlock::Cyrillic => ('\u{0400}', '\u{04FF}'),
+            UnicodeBlock::Hebrew => ('\u{0590}', '\u{05FF}'),
+            UnicodeBlock::Arabic => ('\u{0600}', '\u{06FF}'),
+            UnicodeBlock::Hiragana => ('\u{3040}', '\u{309F}'),
+            UnicodeBlock::Katakana => ('\u{30A0}', '\u{30FF}'),
+            UnicodeBlock::CjkUnifiedIdeographs => ('\u{4E00}', '\u{9FFF}'),
+            UnicodeBlock::HangulSyllables => ('\u{AC00}', '\u{D7AF}'),
+        }
+    }
+}
+
+impl UniformChar {
+    /// Construct a distribution sampling uniformly from a table of assigned,
+    /// printable characters; see [`PrintableChar`] for the ranges included.
+    ///
+    /// [`PrintableChar`]: struct.PrintableChar.html
+    pub fn printable() -> PrintableChar {
+        PrintableChar::new_inclusive('\u{0}', ::core::char::MAX)
+    }
+
+    /// Construct a sampler for all code points in the given Unicode block.
+    pub fn from_block(block: UnicodeBlock) -> UniformChar {
+        let (low, high) = block.bounds();
+        UniformChar::new_inclusive(low, high)
+    }
+
+    /// Construct a distribution over the lowercase letters `'a'` to `'z'`,
+    /// weighted by their frequency in English text, e.g. for generating
+    /// English-like test input.
+    ///
+    /// Note that despite the name, the result is not uniform; see
+    /// [`EnglishLetters`].
+    ///
+    /// [`EnglishLetters`]: struct.EnglishLetters.html
+    #[cfg(feature = "alloc")]
+    pub fn english_letter_frequency() -> EnglishLetters {
+        EnglishLetters {
+            index: WeightedIndex::new(ENGLISH_LETTER_FREQUENCY.iter()).unwrap(),
+        }
+    }
+}
+
+/// Relative frequencies of the letters `a` to `z` in English text, in
+/// thousandths of a percent.
+#[cfg(feature = "alloc")]
+const ENGLISH_LETTER_FREQUENCY: [u32; 26] = [
+    8167, 1492, 2782, 4253, 12702, 2228, 2015, 6094, 6966, 153, 772, 4025, 2406,
+    6749, 7507, 1929, 95, 5987, 6327, 9056, 2758, 978, 2360, 150, 1974, 74,
+];
+
+/// A distribution sampling the lowercase letters `'a'` to `'z'` with their
+/// frequencies in English text, so `'e'` is the most and `'z'` the least
+/// likely.
+///
+/// This `struct` is created by [`UniformChar::english_letter_frequency`].
+///
+/// [`UniformChar::english_letter_frequency`]: struct.UniformChar.html#method.english_letter_frequency
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct EnglishLetters {
+    index: WeightedIndex<u32>,
+}
+
+#[cfg(feature = "alloc")]
+impl Distribution<char> for EnglishLetters {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> char {
+        (b'a' + self.index.sample(rng) as u8) as char
+    }
+}
+
+/// The ranges of characters sampled by `PrintableChar`, as sorted, inclusive
+/// pairs of code points. All are assigned and printable.
+const PRINTABLE_RANGES: [(u32, u32); 21] = [
+    (0x0020, 0x007E), // Basic Latin, without the control characters
+    (0x00A0, 0x00AC), // Latin-1 Supplement, without the C1 controls
+    (0x00AE, 0x024F), // ... and the soft hyphen; Latin Extended-A and -B
+    (0x0391, 0x03A1), // Greek capital letters
+    (0x03A3, 0x03A9),
+    (0x03B1, 0x03C9), // Greek small letters
+    (0x0400, 0x04FF), // Cyrillic
+    (0x05D0, 0x05EA), // Hebrew letters
+    (0x0621, 0x063A), // Arabic letters
+    (0x0641, 0x064A),
+    (0x2010, 0x2027), // General Punctuation, without separators and
+    (0x2030, 0x205E), // formatting characters
+    (0x20A0, 0x20BF), // Currency Symbols
+    (0x2190, 0x21FF), // Arrows
+    (0x2200, 0x22FF), // Mathematical Operators
+    (0x2500, 0x257F), // Box Drawing
+    (0x3041, 0x3096), // Hiragana
+    (0x30A1, 0x30FA), // Katakana
+    (0x4E00, 0x9FA5), // CJK Unified Ideographs (Unicode 1.1)
+    (0xAC00, 0xD7A3), // Hangul Syllables
+    (0x1F600, 0x1F64F), // Emoticons
+];
+
+/// A distribution sampling uniformly from a table of assigned, printable
+/// characters, e.g. for generating display-safe random text.
+///
+/// The table covers printable ASCII, Latin-1 Supplement, Latin Extended-A
+/// and -B, the Greek, Cyrillic, Hebrew and Arabic alphabets, common
+/// punctuation, currency symbols, arrows, mathematical operators, box
+/// drawing, Hiragana, Katakana, the original CJK Unified Ideographs, Hangul
+/// Syllables and emoticons. Excluded are the C0 and C1 control characters,
+/// `DEL`, the surrogates, formatting characters such as the soft hyphen, line
+/// and paragraph separators, private-use and unassigned code points, as well
+/// as all assigned characters outside these ranges. Every character in the
+/// table is equally likely, so most samples are CJK ideographs or Hangul.
+///
+/// This `struct` is created by [`UniformChar::printable`].
+///
+/// [`UniformChar::printable`]: struct.UniformChar.html#method.printable
+#[derive(Clone, Copy, Debug)]
+pub struct PrintableChar {
+    low: u32,
+    high: u32,
+    index: UniformInt<u32>,
+}
+
+impl PrintableChar {
+    /// Construct a distribution sampling the characters in the table which
+    /// are in `[low, high]`.
+    ///
+    /// Panics if there are no such characters.
+    pub fn new_inclusive(low: char, high: char) -> PrintableChar {
+        let (low, high) = (low as u32, high as u32);
+        let mut n = 0;
+        for &(a, b) in PRINTABLE_RANGES.iter() {
+            let (a, b) = (::core::cmp::max(a, low), ::core::cmp::min(b, high));
+            if a <= b {
+                n += b - a + 1;
             }
-            UniformDurationMode::Large { size, secs } => {
-                // constant folding means this is at least as fast as `gen_range`
-                let nano_range = Uniform::new(0, 1_000_000_000);
-                loop {
-                    let d = Duration::new(secs.sample(rng), nano_range.sample(rng));
-                    if d <= size {
-                        break d;
-                    }
+        }
+        assert!(n > 0, "PrintableChar::new_inclusive called without printable characters in range");
+        PrintableChar {
+            low: low,
+            high: high,
+            index: UniformInt::<u32>::new(0, n),
+        }
+    }
+}
+
+impl Distribution<char> for PrintableChar {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> char {
+        let mut i = self.index.sample(rng);
+        for &(a, b) in PRINTABLE_RANGES.iter() {
+            let (a, b) = (::core::cmp::max(a, self.low), ::core::cmp::min(b, self.high));
+            if a <= b {
+                if i <= b - a {
+                    return ::core::char::from_u32(a + i).unwrap();
                 }
+                i -= b - a + 1;
             }
-        };
+        }
+        unreachable!()
+    }
+}
 
-        self.offset + d
+
+
+/// The back-end implementing [`UniformSampler`] for non-zero integers such as
+/// `NonZeroUsize`.
+///
+/// Unless you are implementing [`UniformSampler`] for your own types, this type
+/// should not be used directly, use [`Uniform`] instead.
+///
+/// As the bounds cannot be zero, neither can any sampled value; in particular
+/// `Uniform::new(NonZeroUsize::new(1).unwrap(), len)` samples 1-based indices
+/// in `[1, len)`. To index a slice, which is 0-based, either subtract one or
+/// sample a `usize` from `[0, len)` instead.
+///
+/// This type is only available with the `nonzero_support` feature, as
+/// `NonZeroUsize` requires Rust 1.28.
+///
+/// [`UniformSampler`]: trait.UniformSampler.html
+/// [`Uniform`]: struct.Uniform.html
+#[cfg(feature = "nonzero_support")]
+#[derive(Clone, Copy, Debug)]
+pub struct UniformNonZero<X> {
+    sampler: UniformInt<X>,
+}
+
+#[cfg(feature = "nonzero_support")]
+macro_rules! uniform_nonzero_impl {
+    ($ty:ident, $int:ident) => {
+        impl SampleUniform for $ty {
+            type Sampler = UniformNonZero<$int>;
+        }
+
+        impl UniformSampler for UniformNonZero<$int> {
+            type X = $ty;
+
+            #[inline]
+            fn new<B1, B2>(low_b: B1, high_b: B2) -> Self
+                where B1: SampleBorrow<Self::X> + Sized,
+                      B2: SampleBorrow<Self::X> + Sized
+            {
+                let low = low_b.borrow().get();
+                let high = high_b.borrow().get();
+                UniformNonZero { sampler: UniformInt::<$int>::new(low, high) }
+            }
+
+            #[inline]
+            fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
+                where B1: SampleBorrow<Self::X> + Sized,
+                      B2: SampleBorrow<Self::X> + Sized
+            {
+                let low = low_b.borrow().get();
+                let high = high_b.borrow().get();
+                UniformNonZero {
+                    sampler: UniformInt::<$int>::new_inclusive(low, high)
+                }
+            }
+
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+                // Samples are at least `low`, which is non-zero.
+                $ty::new(self.sampler.sample(rng)).unwrap()
+            }
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use Rng;
-    use distributions::uniform::Uniform;
+#[cfg(feature = "nonzero_support")]
+uniform_nonzero_impl! { NonZeroUsize, usize }
+
+/// The back-end implementing [`UniformSampler`] for `Ipv4Addr`.
+///
+/// Unless you are implementing [`UniformSampler`] for your own types, this type
+/// should not be used directly, use [`Uniform`] instead.
+///
+/// Addresses are ordered as their 32-bit big-endian value, so e.g.
+/// `Uniform::new_inclusive(Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(10, 0, 255, 255))`
+/// samples from the subnet `10.0.0.0/16`.
+///
+/// This type is available with `std`, and without it with the
+/// `core_net_support` feature, as `core::net` requires Rust 1.77.
+///
+/// [`UniformSampler`]: trait.UniformSampler.html
+/// [`Uniform`]: struct.Uniform.html
+#[cfg(any(feature = "std", feature = "core_net_support"))]
+#[derive(Clone, Copy, Debug)]
+pub struct UniformIpv4 {
+    sampler: UniformInt<u32>,
+}
+
+#[cfg(any(feature = "std", feature = "core_net_support"))]
+impl SampleUniform for Ipv4Addr {
+    type Sampler = UniformIpv4;
+}
+
+#[cfg(any(feature = "std", feature = "core_net_support"))]
+impl UniformSampler for UniformIpv4 {
+    type X = Ipv4Addr;
+
+    #[inline]
+    fn new<B1, B2>(low_b: B1, high_b: B2) -> Self
+        where B1: SampleBorrow<Self::X> + Sized,
+              B2: SampleBorrow<Self::X> + Sized
+    {
+        let low = u32::from(*low_b.borrow());
+        let high = u32::from(*high_b.borrow());
+        UniformIpv4 { sampler: UniformInt::<u32>::new(low, high) }
+    }
+
+    #[inline]
+    fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
+        where B1: SampleBorrow<Self::X> + Sized,
+              B2: SampleBorrow<Self::X> + Sized
+    {
+        let low = u32::from(*low_b.borrow());
+        let high = u32::from(*high_b.borrow());
+        UniformIpv4 { sampler: UniformInt::<u32>::new_inclusive(low, high) }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        Ipv4Addr::from(self.sampler.sample(rng))
+    }
+}
+
+
+
+/// The back-end implementing [`UniformSampler`] for `Duration`.
+///
+/// Unless you are implementing [`UniformSampler`] for your own types, this type
+/// should not be used directly, use [`Uniform`] instead.
+///
+/// `Duration` has a resolution of one nanosecond, so the smallest non-empty
+/// half-open range is `[low, low + 1ns)`, which always samples `low`; the
+/// same goes for the closed range `[low, low]`. Like for integers, [`new`]
+/// panics if `low == high` as the range `[low, low)` is empty.
+///
+/// [`UniformSampler`]: trait.UniformSampler.html
+/// [`Uniform`]: struct.Uniform.html
+/// [`new`]: trait.UniformSampler.html#tymethod.new
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct UniformDuration {
+    offset: Duration,
+    mode: UniformDurationMode,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug, Copy, Clone)]
+enum UniformDurationMode {
+    Small {
+        nanos: Uniform<u64>,
+    },
+    #[cfg(not(feature = "i128_support"))]
+    Large {
+        size: Duration,
+        secs: Uniform<u64>,
+    },
+    #[cfg(feature = "i128_support")]
+    Exact128 {
+        nanos: Uniform<u128>,
+    },
+    Log {
+        ln_nanos: Uniform<f64>,
+        low: Duration,
+        high: Duration,
+    },
+    Millis {
+        millis: Uniform<u64>,
+    },
+    Ticks {
+        ticks: Uniform<u64>,
+        tick: Duration,
+    },
+}
+
+#[cfg(feature = "std")]
+impl SampleUniform for Duration {
+    type Sampler = UniformDuration;
+}
+
+#[cfg(feature = "std")]
+impl UniformSampler for UniformDuration {
+    type X = Duration;
+
+    #[inline]
+    fn new<B1, B2>(low_b: B1, high_b: B2) -> Self
+        where B1: SampleBorrow<Self::X> + Sized,
+              B2: SampleBorrow<Self::X> + Sized
+    {
+        let low = *low_b.borrow();
+        let high = *high_b.borrow();
+        assert!(low < high, "Uniform::new called with `low >= high`");
+        UniformDuration::new_inclusive(low, high - Duration::new(0, 1))
+    }
+
+    #[inline]
+    fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
+        where B1: SampleBorrow<Self::X> + Sized,
+              B2: SampleBorrow<Self::X> + Sized
+    {
+        let low = *low_b.borrow();
+        let high = *high_b.borrow();
+        assert!(low <= high, "Uniform::new_inclusive called with `low > high`");
+        let size = high - low;
+        let nanos = size
+            .as_secs()
+            .checked_mul(1_000_000_000)
+            .and_then(|n| n.checked_add(size.subsec_nanos() as u64));
+
+        let mode = match nanos {
+            Some(nanos) => {
+                UniformDurationMode::Small {
+                    nanos: Uniform::new_inclusive(0, nanos),
+                }
+            }
+            #[cfg(not(feature = "i128_support"))]
+            None => {
+                UniformDurationMode::Large {
+                    size: size,
+                    secs: Uniform::new_inclusive(0, size.as_secs()),
+                }
+            }
+            #[cfg(feature = "i128_support")]
+            None => {
+                let nanos = size.as_secs() as u128 * 1_000_000_000
+                    + size.subsec_nanos() as u128;
+                UniformDurationMode::Exact128 {
+                    nanos: Uniform::new_inclusive(0, nanos),
+                }
+            }
+        };
+
+        UniformDuration {
+            mode,
+            offset: low,
+        }
+    }
+
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Duration {
+        let d = match self.mode {
+            UniformDurationMode::Small { nanos } => {
+                let nanos = nanos.sample(rng);
+                Duration::new(nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32)
+            }
+            #[cfg(not(feature = "i128_support"))]
+            UniformDurationMode::Large { size, secs } => {
+                // constant folding means this is at least as fast as `gen_range`
+                let nano_range = Uniform::new(0, 1_000_000_000);
+                loop {
+                    let d = Duration::new(secs.sample(rng), nano_range.sample(rng));
+                    if d <= size {
+                        break d;
+                    }
+                }
+            }
+            #[cfg(feature = "i128_support")]
+            UniformDurationMode::Exact128 { nanos } => {
+                // A single sample; no rejection loop as for `Large`.
+                let nanos = nanos.sample(rng);
+                Duration::new((nanos / 1_000_000_000) as u64,
+                              (nanos % 1_000_000_000) as u32)
+            }
+            UniformDurationMode::Millis { millis } => {
+                Duration::from_millis(millis.sample(rng))
+            }
+            UniformDurationMode::Ticks { ticks, tick } => {
+                duration_mul_saturating(tick, ticks.sample(rng))
+            }
+            UniformDurationMode::Log { ln_nanos, low, high } => {
+                let nanos = ln_nanos.sample(rng).exp();
+                let secs = (nanos / 1e9).floor();
+                // After rounding the remainder may be slightly out of range;
+                // clamp it, as casting an out-of-range float to an integer
+                // is undefined behaviour with older compilers.
+                let subsec = nanos - secs * 1e9;
+                let subsec = if subsec < 0.0 {
+                    0.0
+                } else if subsec > 999_999_999.0 {
+                    999_999_999.0
+                } else {
+                    subsec
+                };
+                let d = if secs < ::core::u64::MAX as f64 {
+                    Duration::new(secs as u64, subsec as u32)
+                } else {
+                    high
+                };
+                // Guard against rounding errors in `ln` and `exp`.
+                if d < low {
+                    low
+                } else if d >= high {
+                    high - Duration::new(0, 1)
+                } else {
+                    d
+                }
+            }
+        };
+
+        self.offset + d
+    }
+}
+
+#[cfg(feature = "std")]
+impl UniformDuration {
+    /// Sample a single value uniformly from the closed range `[low, high]`,
+    /// including `high`. Panics if `low > high`.
+    ///
+    /// This is the inclusive counterpart of [`sample_single`].
+    ///
+    /// [`sample_single`]: trait.UniformSampler.html#method.sample_single
+    pub fn sample_single_inclusive<R: Rng + ?Sized, B1, B2>(low: B1, high: B2, rng: &mut R)
+        -> Duration
+        where B1: SampleBorrow<Duration> + Sized,
+              B2: SampleBorrow<Duration> + Sized
+    {
+        let uniform = UniformDuration::new_inclusive(low, high);
+        uniform.sample(rng)
+    }
+
+    /// Construct a sampler for a whole number of milliseconds in
+    /// `[low_ms, high_ms)`. Panics if `low_ms >= high_ms`.
+    ///
+    /// This is faster than sampling with nanosecond precision when only
+    /// millisecond precision is needed.
+    pub fn new_millis(low_ms: u64, high_ms: u64) -> UniformDuration {
+        assert!(low_ms < high_ms,
+                "UniformDuration::new_millis called with `low_ms >= high_ms`");
+        UniformDuration {
+            mode: UniformDurationMode::Millis {
+                millis: Uniform::new(low_ms, high_ms),
+            },
+            offset: Duration::new(0, 0),
+        }
+    }
+
+    /// Construct a sampler for a whole number of ticks in
+    /// `[low_ticks, high_ticks)`, returning that many times `tick`, e.g. for
+    /// simulations with a fixed time quantum. Results which do not fit in a
+    /// `Duration` saturate at its largest value.
+    ///
+    /// Panics if `low_ticks >= high_ticks`.
+    pub fn new_ticks(low_ticks: u64, high_ticks: u64, tick: Duration) -> UniformDuration {
+        assert!(low_ticks < high_ticks,
+                "UniformDuration::new_ticks called with `low_ticks >= high_ticks`");
+        UniformDuration {
+            mode: UniformDurationMode::Ticks {
+                ticks: Uniform::new(low_ticks, high_ticks),
+                tick: tick,
+            },
+            offset: Duration::new(0, 0),
+        }
+    }
+
+    /// Construct a sampler for durations in `[low, high)` distributed
+    /// log-uniformly, i.e. the logarithm of the number of nanoseconds is
+    /// uniformly distributed and each order of magnitude is equally likely.
+    /// If `low` and `high` are so close that their logarithms round to the
+    /// same value, this samples `[low, high)` uniformly instead.
+    ///
+    /// Panics if `low` is zero or `low >= high`.
+    pub fn new_log(low: Duration, high: Duration) -> UniformDuration {
+        assert!(low > Duration::new(0, 0),
+                "UniformDuration::new_log called with `low == 0`");
+        assert!(low < high, "UniformDuration::new_log called with `low >= high`");
+        let ln_nanos = |d: Duration| {
+            (d.as_secs() as f64 * 1e9 + d.subsec_nanos() as f64).ln()
+        };
+        let (ln_low, ln_high) = (ln_nanos(low), ln_nanos(high));
+        if ln_low >= ln_high {
+            return <UniformDuration as UniformSampler>::new(low, high);
+        }
+        UniformDuration {
+            mode: UniformDurationMode::Log {
+                ln_nanos: Uniform::new(ln_low, ln_high),
+                low: low,
+                high: high,
+            },
+            offset: Duration::new(0, 0),
+        }
+    }
+}
+
+/// Multiply `d` by `n`, saturating at the largest `Duration`.
+#[cfg(feature = "std")]
+fn duration_mul_saturating(d: Duration, n: u64) -> Duration {
+    let max = Duration::new(::core::u64::MAX, 999_999_999);
+    let secs = match d.as_secs().checked_mul(n) {
+        Some(secs) => secs,
+        None => return max,
+    };
+    // Split `n` so that the products with the nanoseconds cannot overflow.
+    let nanos = d.subsec_nanos() as u64;
+    let high = nanos * (n / 1_000_000_000);
+    let low = nanos * (n % 1_000_000_000);
+    match secs.checked_add(high).and_then(|s| s.checked_add(low / 1_000_000_000)) {
+        Some(secs) => Duration::new(secs, (low % 1_000_000_000) as u32),
+        None => max,
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Types whose values can be enumerated in order, one step at a time.
+///
+/// Implementing this trait for a type with an [`Ord`] implementation allows
+/// sampling ranges of it with [`UniformOrd`], without implementing
+/// [`UniformSampler`] by hand. Implementations are provided for the primitive
+/// integer types up to 64 bits and for `char`.
+///
+/// `successor` and `distance` must be consistent with the ordering: if
+/// `a <= b`, applying `successor` to `a` exactly `a.distance(&b)` times must
+/// give `b`.
+///
+/// [`Ord`]: https://doc.rust-lang.org/std/cmp/trait.Ord.html
+/// [`UniformOrd`]: struct.UniformOrd.html
+/// [`UniformSampler`]: trait.UniformSampler.html
+pub trait Countable: Ord + Clone {
+    /// The next value after `self`, or `None` if `self` is the largest value.
+    fn successor(&self) -> Option<Self>;
+
+    /// The number of steps from `self` to `other`, where `self <= other`.
+    fn distance(&self, other: &Self) -> u64;
+
+    /// The value `n` steps after `self`.
+    ///
+    /// The default implementation applies `successor` `n` times; types which
+    /// can do this in constant time should override it.
+    fn advance(&self, n: u64) -> Self {
+        let mut x = self.clone();
+        for _ in 0..n {
+            x = x.successor().expect("Countable::advance past the largest value");
+        }
+        x
+    }
+}
+
+macro_rules! countable_int_impl {
+    ($ty:ty, $unsigned:ty) => {
+        impl Countable for $ty {
+            fn successor(&self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn distance(&self, other: &Self) -> u64 {
+                other.wrapping_sub(*self) as $unsigned as u64
+            }
+
+            fn advance(&self, n: u64) -> Self {
+                self.wrapping_add(n as $unsigned as $ty)
+            }
+        }
+    }
+}
+
+countable_int_impl! { i8, u8 }
+countable_int_impl! { i16, u16 }
+countable_int_impl! { i32, u32 }
+countable_int_impl! { i64, u64 }
+countable_int_impl! { isize, usize }
+countable_int_impl! { u8, u8 }
+countable_int_impl! { u16, u16 }
+countable_int_impl! { u32, u32 }
+countable_int_impl! { u64, u64 }
+countable_int_impl! { usize, usize }
+
+impl Countable for char {
+    fn successor(&self) -> Option<Self> {
+        if *self == ::core::char::MAX {
+            None
+        } else {
+            Some(self.advance(1))
+        }
+    }
+
+    fn distance(&self, other: &Self) -> u64 {
+        (char_to_comp_u32(*other) - char_to_comp_u32(*self)) as u64
+    }
+
+    fn advance(&self, n: u64) -> Self {
+        let mut x = char_to_comp_u32(*self) + n as u32;
+        if x >= CHAR_SURROGATE_START {
+            x += CHAR_SURROGATE_LEN;
+        }
+        ::core::char::from_u32(x).expect("Countable::advance past the largest value")
+    }
+}
+
+/// The back-end implementing [`UniformSampler`] for any type implementing
+/// [`Countable`].
+///
+/// The number of steps between `low` and `high` is sampled uniformly with a
+/// [`UniformInt<u64>`], and `low` is advanced by that many steps. To use it
+/// with [`Uniform`] and [`Rng::gen_range`], implement [`SampleUniform`] for
+/// the type with `type Sampler = UniformOrd<T>`.
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand::distributions::uniform::{Countable, SampleUniform, UniformOrd};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// struct NodeId(u32);
+///
+/// impl Countable for NodeId {
+///     fn successor(&self) -> Option<Self> {
+///         self.0.checked_add(1).map(NodeId)
+///     }
+///     fn distance(&self, other: &Self) -> u64 {
+///         (other.0 - self.0) as u64
+///     }
+/// }
+///
+/// impl SampleUniform for NodeId {
+///     type Sampler = UniformOrd<NodeId>;
+/// }
+///
+/// let node = rand::thread_rng().gen_range(NodeId(10), NodeId(20));
+/// assert!(NodeId(10) <= node && node < NodeId(20));
+/// ```
+///
+/// [`UniformSampler`]: trait.UniformSampler.html
+/// [`Countable`]: trait.Countable.html
+/// [`UniformInt<u64>`]: struct.UniformInt.html
+/// [`Uniform`]: struct.Uniform.html
+/// [`Rng::gen_range`]: ../../trait.Rng.html#method.gen_range
+/// [`SampleUniform`]: trait.SampleUniform.html
+#[derive(Clone, Copy, Debug)]
+pub struct UniformOrd<T> {
+    low: T,
+    steps: UniformInt<u64>,
+}
+
+impl<T: Countable> UniformSampler for UniformOrd<T> {
+    type X = T;
+
+    fn new<B1, B2>(low_b: B1, high_b: B2) -> Self
+        where B1: SampleBorrow<Self::X> + Sized,
+              B2: SampleBorrow<Self::X> + Sized
+    {
+        let low = low_b.borrow().clone();
+        let high = high_b.borrow();
+        assert!(low < *high, "Uniform::new called with `low >= high`");
+        let distance = low.distance(high);
+        UniformOrd {
+            low: low,
+            steps: UniformInt::<u64>::new(0, distance),
+        }
+    }
+
+    fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
+        where B1: SampleBorrow<Self::X> + Sized,
+              B2: SampleBorrow<Self::X> + Sized
+    {
+        let low = low_b.borrow().clone();
+        let high = high_b.borrow();
+        assert!(low <= *high, "Uniform::new_inclusive called with `low > high`");
+        let distance = low.distance(high);
+        UniformOrd {
+            low: low,
+            steps: UniformInt::<u64>::new_inclusive(0, distance),
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        self.low.advance(self.steps.sample(rng))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+// Helper functions built on the uniform back-ends.
+
+
+/// Sample a point `(u, v)` uniformly from the right triangle with corners
+/// `(0, 0)`, `(1, 0)` and `(0, 1)`, i.e. with `u >= 0`, `v >= 0` and
+/// `u + v <= 1`.
+///
+/// This can be used to sample barycentric coordinates of an arbitrary
+/// triangle. A point is sampled from the unit square; if it falls outside the
+/// triangle it is reflected into the other half of the square, so no samples
+/// are rejected.
+pub fn sample_triangle<R: Rng + ?Sized>(rng: &mut R) -> (f64, f64) {
+    let u: f64 = rng.gen();
+    let v: f64 = rng.gen();
+    if u + v > 1.0 {
+        (1.0 - u, 1.0 - v)
+    } else {
+        (u, v)
+    }
+}
+
+/// Sample a point `(x, y)` uniformly from the disk of the given `radius`
+/// centered at the origin. Panics if `radius` is not positive.
+///
+/// A radius and an angle are sampled uniformly; the radius is transformed with
+/// a square root so that the points have a uniform density over the area of
+/// the disk, rather than being concentrated towards the center.
+#[cfg(feature = "std")]
+pub fn sample_disk<R: Rng + ?Sized>(rng: &mut R, radius: f64) -> (f64, f64) {
+    assert!(radius > 0.0, "sample_disk called with `radius <= 0`");
+    let r = radius * UniformFloat::<f64>::new(0.0, 1.0).sample(rng).sqrt();
+    let theta = UniformFloat::<f64>::new(0.0, 2.0 * ::std::f64::consts::PI).sample(rng);
+    (r * theta.cos(), r * theta.sin())
+}
+
+/// Sample a point `(x, y)` uniformly from the annulus (ring) between the
+/// circles of radius `r_inner` and `r_outer` centered at the origin.
+///
+/// As for [`sample_disk`], the radius is transformed so that the points have a
+/// uniform density over the area: `r = sqrt(u * (r_outer² - r_inner²) +
+/// r_inner²)`. Panics unless `0 <= r_inner < r_outer`.
+///
+/// [`sample_disk`]: fn.sample_disk.html
+#[cfg(feature = "std")]
+pub fn sample_annulus<R: Rng + ?Sized>(rng: &mut R, r_inner: f64, r_outer: f64) -> (f64, f64) {
+    assert!(0.0 <= r_inner && r_inner < r_outer,
+            "sample_annulus called without `0 <= r_inner < r_outer`");
+    let inner2 = r_inner * r_inner;
+    let u = UniformFloat::<f64>::new(0.0, 1.0).sample(rng);
+    let r = (u * (r_outer * r_outer - inner2) + inner2).sqrt();
+    let theta = UniformFloat::<f64>::new(0.0, 2.0 * ::std::f64::consts::PI).sample(rng);
+    (r * theta.cos(), r * theta.sin())
+}
+
+/// Sample a point `[x, y, z]` uniformly from the surface of the unit sphere,
+/// i.e. a random direction in three dimensions.
+///
+/// By Archimedes' hat-box theorem, the height `z` of a uniform point on the
+/// sphere is uniform in `[-1, 1]`; it is combined with a uniform angle around
+/// the `z` axis.
+#[cfg(feature = "std")]
+pub fn sample_sphere_surface<R: Rng + ?Sized>(rng: &mut R) -> [f64; 3] {
+    let z = UniformFloat::<f64>::new(-1.0, 1.0).sample(rng);
+    let theta = UniformFloat::<f64>::new(0.0, 2.0 * ::std::f64::consts::PI).sample(rng);
+    let r = (1.0 - z * z).sqrt();
+    [r * theta.cos(), r * theta.sin(), z]
+}
+
+/// Scale `base` by a factor sampled uniformly from `[low_frac, high_frac)`,
+/// e.g. to add jitter to a timeout with `sample_scaled(rng, base, 0.8, 1.2)`.
+///
+/// The result saturates at the largest representable `Duration`. Because the
+/// factor is a floating-point value, the result is accurate to about a
+/// nanosecond only for durations up to a few weeks.
+///
+/// # Panics
+///
+/// If `low_frac` is not positive or `low_frac >= high_frac`.
+#[cfg(feature = "std")]
+pub fn sample_scaled<R: Rng + ?Sized>(rng: &mut R, base: Duration, low_frac: f64, high_frac: f64)
+    -> Duration
+{
+    assert!(low_frac > 0.0, "sample_scaled called with `low_frac <= 0`");
+    let factor = UniformFloat::<f64>::sample_single(low_frac, high_frac, rng);
+    let max = Duration::new(::core::u64::MAX, 999_999_999);
+
+    let secs = base.as_secs() as f64 * factor;
+    if secs >= ::core::u64::MAX as f64 {
+        return max;
+    }
+    let whole = secs.trunc();
+    let nanos = (secs - whole) * 1e9 + base.subsec_nanos() as f64 * factor;
+    if nanos >= ::core::u64::MAX as f64 {
+        return max;
+    }
+    let nanos = nanos as u64;
+    let extra = Duration::new(nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32);
+    Duration::new(whole as u64, 0).checked_add(extra).unwrap_or(max)
+}
+
+/// Sample an exponentially distributed `Duration` with the given `mean`, e.g.
+/// for the time between events arriving at random.
+///
+/// This uses the inverse of the cumulative distribution function:
+/// `-mean * ln(1 - u)`, with `u` sampled uniformly from `[0, 1)`. The result
+/// saturates at the largest representable `Duration`.
+#[cfg(feature = "std")]
+pub fn sample_exp_duration<R: Rng + ?Sized>(rng: &mut R, mean: Duration) -> Duration {
+    let u = UniformFloat::<f64>::sample_single(0.0, 1.0, rng);
+    let mean = mean.as_secs() as f64 + mean.subsec_nanos() as f64 * 1e-9;
+    let secs = -mean * (1.0 - u).ln();
+    if secs >= ::core::u64::MAX as f64 {
+        return Duration::new(::core::u64::MAX, 999_999_999);
+    }
+    let whole = secs.trunc();
+    let nanos = ((secs - whole) * 1e9) as u32;
+    Duration::new(whole as u64, ::core::cmp::min(nanos, 999_999_999))
+}
+
+/// Generate a random string of valid UTF-8 of at most `max_bytes` bytes.
+///
+/// `char`s are sampled uniformly from all Unicode scalar values and appended
+/// until the next one would not fit. As most scalar values are outside the
+/// Basic Multilingual Plane, most characters take four bytes; the string may
+/// be shorter than `max_bytes` by up to three bytes.
+#[cfg(feature = "alloc")]
+pub fn random_utf8<R: Rng + ?Sized>(rng: &mut R, max_bytes: usize) -> String {
+    let distr = Uniform::new_inclusive('\0', ::core::char::MAX);
+    let mut s = String::with_capacity(max_bytes);
+    loop {
+        let c = distr.sample(rng);
+        if s.len() + c.len_utf8() > max_bytes {
+            return s;
+        }
+        s.push(c);
+    }
+}
+
+/// Generate random bytes where only the bits set in `mask` are random, and
+/// all other bits are taken from `fixed`, e.g. for identifiers with fixed
+/// version or variant bits such as UUIDs.
+///
+/// Panics if `fixed` and `mask` have different lengths.
+#[cfg(feature = "alloc")]
+pub fn sample_bytes_masked<R: Rng + ?Sized>(rng: &mut R, fixed: &[u8], mask: &[u8]) -> Vec<u8> {
+    assert_eq!(fixed.len(), mask.len(),
+               "sample_bytes_masked called with `fixed` and `mask` of different lengths");
+    let mut bytes = Vec::with_capacity(fixed.len());
+    bytes.resize(fixed.len(), 0u8);
+    rng.fill_bytes(&mut bytes);
+    for ((b, &f), &m) in bytes.iter_mut().zip(fixed.iter()).zip(mask.iter()) {
+        *b = (*b & m) | (f & !m);
+    }
+    bytes
+}
+
+/// Generate a big-endian byte string of the same length as `upper`, uniformly
+/// distributed over all values strictly less than `upper`, e.g. for integers
+/// wider than the primitive types.
+///
+/// Leading zero bytes of `upper` are kept, and the first non-zero byte is
+/// masked to the smallest number of bits which can hold it, so that fewer
+/// than half of the candidates are rejected on average.
+///
+/// Panics if `upper` is zero, i.e. if all bytes are zero or it is empty.
+#[cfg(feature = "alloc")]
+pub fn sample_bytes_below<R: Rng + ?Sized>(rng: &mut R, upper: &[u8]) -> Vec<u8> {
+    let start = match upper.iter().position(|&b| b != 0) {
+        Some(start) => start,
+        None => panic!("sample_bytes_below called with `upper` equal to zero"),
+    };
+    let mask = !0u8 >> upper[start].leading_zeros();
+    let mut bytes = Vec::with_capacity(upper.len());
+    bytes.resize(upper.len(), 0u8);
+    loop {
+        rng.fill_bytes(&mut bytes[start..]);
+        bytes[start] &= mask;
+        // Slices compare lexicographically, i.e. as big-endian integers of
+        // equal length.
+        if bytes[start..] < upper[start..] {
+            return bytes;
+        }
+    }
+}
+
+/// Generate a `rows` × `cols` matrix in row-major order with entries sampled
+/// uniformly from `[low, high)`, e.g. for testing linear algebra code.
+///
+/// Panics if `low >= high`.
+#[cfg(feature = "alloc")]
+pub fn sample_matrix<R: Rng + ?Sized>(rng: &mut R, rows: usize, cols: usize, low: f64, high: f64)
+    -> Vec<f64>
+{
+    assert!(low < high, "sample_matrix called with `low >= high`");
+    let distr = UniformFloat::<f64>::new(low, high);
+    (0..rows * cols).map(|_| distr.sample(rng)).collect()
+}
+
+/// Add triangular dither in `(-1, 1)` to `value`, e.g. before rounding it to
+/// an integer.
+///
+/// The dither is the difference of two uniform samples from `[0, 1)`, which
+/// has a triangular probability density with mean zero. Quantizing with such
+/// dither decorrelates the quantization error from the signal, avoiding
+/// banding in images and distortion in audio.
+pub fn dither<R: Rng + ?Sized>(rng: &mut R, value: f64) -> f64 {
+    let a: f64 = rng.gen();
+    let b: f64 = rng.gen();
+    value + (a - b)
+}
+
+/// Return `true` with a probability of exactly `numerator / denominator`.
+///
+/// Unlike [`Rng::gen_ratio`], which converts the ratio to a floating-point
+/// probability, this compares `gen_range(0, denominator)` with `numerator`
+/// so the probability is exact, at the cost of being slower.
+///
+/// # Panics
+///
+/// If `denominator == 0` or `numerator > denominator`.
+///
+/// [`Rng::gen_ratio`]: ../../trait.Rng.html#method.gen_ratio
+pub fn gen_ratio_bool<R: Rng + ?Sized>(rng: &mut R, numerator: u32, denominator: u32)
+    -> bool
+{
+    assert!(numerator <= denominator,
+            "gen_ratio_bool called with `numerator > denominator`");
+    rng.gen_range(0, denominator) < numerator
+}
+
+/// An error returned by [`try_gen_range`] for invalid bounds.
+///
+/// [`try_gen_range`]: fn.try_gen_range.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UniformError {
+    /// The range is empty: `low >= high`, or the bounds are not comparable.
+    EmptyRange,
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl UniformError {
+    fn description(&self) -> &'static str {
+        match *self {
+            UniformError::EmptyRange => "low bound not below high bound",
+            UniformError::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
+impl fmt::Display for UniformError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description())
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for UniformError {
+    fn description(&self) -> &str {
+        self.description()
+    }
+}
+
+/// Sample a value from the half-open range `[low, high)`, like
+/// [`Rng::gen_range`], but return an error instead of panicking if the range
+/// is empty.
+///
+/// Like `gen_range` this uses `UniformSampler::sample_single`, so is suited
+/// to sampling a single value from bounds only known at runtime.
+///
+/// [`Rng::gen_range`]: ../../trait.Rng.html#method.gen_range
+pub fn try_gen_range<T, R, B1, B2>(rng: &mut R, low: B1, high: B2) -> Result<T, UniformError>
+    where T: SampleUniform + PartialOrd, R: Rng + ?Sized,
+          B1: SampleBorrow<T> + Sized, B2: SampleBorrow<T> + Sized
+{
+    if !(low.borrow() < high.borrow()) {
+        return Err(UniformError::EmptyRange);
+    }
+    Ok(T::Sampler::sample_single(low, high, rng))
+}
+
+/// Sample a port number uniformly from the dynamic or private (ephemeral)
+/// range `[49152, 65535]` reserved by IANA, e.g. for network fuzzing.
+pub fn sample_ephemeral_port<R: Rng + ?Sized>(rng: &mut R) -> u16 {
+    UniformInt::<u16>::new_inclusive(49152, 65535).sample(rng)
+}
+
+/// Sample a port number uniformly from `[1024, 65535]`, i.e. any port which
+/// does not require elevated privileges to bind to on most systems.
+pub fn sample_unprivileged_port<R: Rng + ?Sized>(rng: &mut R) -> u16 {
+    UniformInt::<u16>::new_inclusive(1024, 65535).sample(rng)
+}
+
+/// Sample an index into a collection of length `len`, uniformly from
+/// `[0, len)`.
+///
+/// This is the same as `rng.gen_range(0, len)`, but panics with a message
+/// explaining the problem if `len == 0`.
+pub fn gen_index<R: Rng + ?Sized>(rng: &mut R, len: usize) -> usize {
+    assert!(len > 0, "cannot sample index from empty collection");
+    UniformInt::<usize>::sample_single(0, len, rng)
+}
+
+/// Sample a random, possibly empty, subrange `start..end` of `0..len`, e.g.
+/// to pick a sub-slice of a buffer when fuzzing.
+///
+/// `start` is sampled uniformly from `[0, len]`, then `end` uniformly from
+/// `[start, len]`. Note that this does not make all subranges equally
+/// likely: short ranges near the end are favoured.
+pub fn sample_subrange<R: Rng + ?Sized>(rng: &mut R, len: usize) -> ::core::ops::Range<usize> {
+    let start = UniformInt::<usize>::new_inclusive(0, len).sample(rng);
+    let end = UniformInt::<usize>::new_inclusive(start, len).sample(rng);
+    start..end
+}
+
+/// Sample an index in `[0, len)` which is a multiple of `stride`, e.g. the
+/// start of a random block or cache line in a buffer.
+///
+/// Only the `len / stride` complete strides are considered, so an incomplete
+/// block at the end of the buffer is never chosen.
+///
+/// # Panics
+///
+/// If `stride == 0` or `stride > len`.
+pub fn sample_strided_index<R: Rng + ?Sized>(rng: &mut R, len: usize, stride: usize) -> usize {
+    assert!(stride > 0, "sample_strided_index called with `stride == 0`");
+    assert!(stride <= len, "sample_strided_index called with `stride > len`");
+    UniformInt::<usize>::sample_single(0, len / stride, rng) * stride
+}
+
+/// Sample `n` indices into a collection of length `len` with replacement,
+/// e.g. to draw a bootstrap resample of a data set.
+///
+/// Panics if `len == 0` and `n > 0`.
+#[cfg(feature = "alloc")]
+pub fn bootstrap_indices<R: Rng + ?Sized>(rng: &mut R, len: usize, n: usize) -> Vec<usize> {
+    assert!(len > 0 || n == 0, "cannot sample index from empty collection");
+    let mut indices = Vec::with_capacity(n);
+    for _ in 0..n {
+        indices.push(UniformInt::<usize>::sample_single(0, len, rng));
+    }
+    indices
+}
+
+/// Sample an `Ordering` with probabilities proportional to the weights of
+/// `Less`, `Equal` and `Greater`, e.g. to fuzz comparison-driven code with a
+/// realistic mix of outcomes.
+///
+/// Panics if all weights are zero.
+pub fn sample_weighted_ordering<R: Rng + ?Sized>(rng: &mut R, w_less: u32, w_eq: u32, w_gt: u32)
+    -> Ordering
+{
+    let total = w_less as u64 + w_eq as u64 + w_gt as u64;
+    assert!(total > 0, "sample_weighted_ordering called with a total weight of zero");
+    let chosen = UniformInt::<u64>::sample_single(0, total, rng);
+    if chosen < w_less as u64 {
+        Ordering::Less
+    } else if chosen < w_less as u64 + w_eq as u64 {
+        Ordering::Equal
+    } else {
+        Ordering::Greater
+    }
+}
+
+/// Sample an index into `weights`, where the probability of each index is
+/// proportional to its weight. Panics if all weights are zero.
+///
+/// The weights are summed, a value is sampled uniformly from `[0, total)`,
+/// and the index is found with a linear scan. This takes `O(n)` time for
+/// every sample; for large slices sampled repeatedly, compute the running
+/// totals once and use [`weighted_index_cumulative`] instead.
+///
+/// [`weighted_index_cumulative`]: fn.weighted_index_cumulative.html
+pub fn weighted_index<R: Rng + ?Sized>(rng: &mut R, weights: &[u32]) -> usize {
+    let total = weights.iter().fold(0u64, |sum, &w| sum + w as u64);
+    assert!(total > 0, "weighted_index called with a total weight of zero");
+    let mut chosen = rng.gen_range(0, total);
+    for (i, &w) in weights.iter().enumerate() {
+        if chosen < w as u64 {
+            return i;
+        }
+        chosen -= w as u64;
+    }
+    unreachable!()
+}
+
+/// Sample an index like [`weighted_index`], given the running totals of the
+/// weights: `cumulative[i]` is the sum of the weights of indices `0..=i`, so
+/// the slice is non-decreasing. The index is found with a binary search,
+/// taking `O(log n)` time per sample.
+///
+/// Panics if `cumulative` is empty or its last element (the total weight) is
+/// zero.
+///
+/// [`weighted_index`]: fn.weighted_index.html
+pub fn weighted_index_cumulative<R: Rng + ?Sized>(rng: &mut R, cumulative: &[u64]) -> usize {
+    let total = cumulative.last().cloned().unwrap_or(0);
+    assert!(total > 0, "weighted_index_cumulative called with a total weight of zero");
+    let chosen = rng.gen_range(0, total);
+    // The first index whose running total exceeds `chosen`; this skips
+    // indices with weight zero, whose total equals the one before.
+    match cumulative.binary_search_by(|&c| if c <= chosen { Ordering::Less }
+                                           else { Ordering::Greater }) {
+        Ok(i) | Err(i) => i,
+    }
+}
+
+/// Sample a point of an N-dimensional integer lattice, filling `out` with one
+/// coordinate per dimension. Coordinate `i` is sampled independently and
+/// uniformly from `[lows[i], highs[i])`.
+///
+/// # Panics
+///
+/// If `lows`, `highs` and `out` do not all have the same length, or if
+/// `lows[i] >= highs[i]` for any dimension.
+pub fn sample_grid<R: Rng + ?Sized>(rng: &mut R, lows: &[i64], highs: &[i64], out: &mut [i64]) {
+    assert!(lows.len() == highs.len() && lows.len() == out.len(),
+            "sample_grid called with slices of different lengths");
+    for ((x, &low), &high) in out.iter_mut().zip(lows.iter()).zip(highs.iter()) {
+        assert!(low < high, "sample_grid called with `low >= high`");
+        *x = UniformInt::<i64>::sample_single(low, high, rng);
+    }
+}
+
+/// Randomly produce `Ok` with probability `p_ok`, and `Err` otherwise, e.g.
+/// to exercise error handling paths in tests or fuzzing.
+///
+/// The value is produced by calling `ok` or `err` respectively, which are
+/// passed the RNG so they can generate random contents themselves. Panics if
+/// `p_ok` is not in `[0, 1]`; see [`Rng::gen_bool`].
+///
+/// [`Rng::gen_bool`]: ../../trait.Rng.html#method.gen_bool
+pub fn sample_result<R, T, E, F, G>(rng: &mut R, p_ok: f64, ok: F, err: G) -> Result<T, E>
+    where R: Rng + ?Sized, F: FnOnce(&mut R) -> T, G: FnOnce(&mut R) -> E
+{
+    if rng.gen_bool(p_ok) {
+        Ok(ok(rng))
+    } else {
+        Err(err(rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Rng, RngCore, Error};
+    use rand_core::impls;
+    use distributions::uniform::Uniform;
+
+    /// An RNG returning a fixed sequence of words and counting how many were
+    /// used, for deterministic tests of rejection sampling. Where a `u32` is
+    /// needed, the low 32 bits of the word are used. Panics if the words run
+    /// out.
+    struct CountingRng<'a> {
+        words: &'a [u64],
+        used: usize,
+    }
+
+    impl<'a> CountingRng<'a> {
+        fn new(words: &'a [u64]) -> CountingRng<'a> {
+            CountingRng { words: words, used: 0 }
+        }
+    }
+
+    impl<'a> RngCore for CountingRng<'a> {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+        fn next_u64(&mut self) -> u64 {
+            let word = self.words[self.used];
+            self.used += 1;
+            word
+        }
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            impls::fill_bytes_via_next(self, dest)
+        }
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+            Ok(self.fill_bytes(dest))
+        }
+    }
+
+    /// Call `sample` `samples` times, counting how many RNG draws each call
+    /// reported: index `i` holds the number of samples which took `i + 1`
+    /// draws, with the last bucket also counting anything more.
+    fn retry_histogram<F: FnMut(&mut u64)>(samples: usize, mut sample: F) -> [u64; 8] {
+        let mut histogram = [0u64; 8];
+        for _ in 0..samples {
+            let mut draws = 0;
+            sample(&mut draws);
+            histogram[::core::cmp::min(draws, 8) as usize - 1] += 1;
+        }
+        histogram
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_uniform_bad_limits_equal_int() {
+        Uniform::new(10, 10);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_uniform_bad_limits_equal_float() {
+        Uniform::new(10., 10.);
+    }
+
+    #[test]
+    fn test_uniform_good_limits_equal_int() {
+        let mut rng = ::test::rng(804);
+        let dist = Uniform::new_inclusive(10, 10);
+        for _ in 0..20 {
+            assert_eq!(rng.sample(dist), 10);
+        }
+    }
+
+    #[test]
+    fn test_uniform_good_limits_equal_float() {
+        let mut rng = ::test::rng(805);
+        let dist = Uniform::new_inclusive(10., 10.);
+        for _ in 0..20 {
+            assert_eq!(rng.sample(dist), 10.);
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_uniform_bad_limits_flipped_int() {
+        Uniform::new(10, 5);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_uniform_bad_limits_flipped_float() {
+        Uniform::new(10., 5.);
+    }
+
+    #[test]
+    fn test_integers() {
+        let mut rng = ::test::rng(251);
+        macro_rules! t {
+            ($($ty:ident),*) => {{
+                $(
+                   let v: &[($ty, $ty)] = &[(0, 10),
+                                            (10, 127),
+                                            (::core::$ty::MIN, ::core::$ty::MAX)];
+                   for &(low, high) in v.iter() {
+                        let my_uniform = Uniform::new(low, high);
+                        for _ in 0..1000 {
+                            let v: $ty = rng.sample(my_uniform);
+                            assert!(low <= v && v < high);
+                        }
+
+                        let my_uniform = Uniform::new_inclusive(low, high);
+                        for _ in 0..1000 {
+                            let v: $ty = rng.sample(my_uniform);
+                            assert!(low <= v && v <= high);
+                        }
+
+                        let my_uniform = Uniform::new(&low, high);
+                        for _ in 0..1000 {
+                            let v: $ty = rng.sample(my_uniform);
+                            assert!(low <= v && v < high);
+                        }
+
+                        let my_uniform = Uniform::new_inclusive(&low, &high);
+                        for _ in 0..1000 {
+                            let v: $ty = rng.sample(my_uniform);
+                            assert!(low <= v && v <= high);
+                        }
+
+                        for _ in 0..1000 {
+                            let v: $ty = rng.gen_range(low, high);
+                            assert!(low <= v && v < high);
+                        }
+                    }
+                 )*
+            }}
+        }
+        t!(i8, i16, i32, i64, isize,
+           u8, u16, u32, u64, usize);
+        #[cfg(feature = "i128_support")]
+        t!(i128, u128)
+    }
+
+    #[test]
+    fn test_floats() {
+        let mut rng = ::test::rng(252);
+        macro_rules! t {
+            ($($ty:ty),*) => {{
+                $(
+                   let v: &[($ty, $ty)] = &[(0.0, 100.0),
+                                            (-1e35, -1e25),
+                                            (1e-35, 1e-25),
+                                            (-1e35, 1e35)];
+                   for &(low, high) in v.iter() {
+                        let my_uniform = Uniform::new(low, high);
+                        for _ in 0..1000 {
+                            let v: $ty = rng.sample(my_uniform);
+                            assert!(low <= v && v < high);
+                        }
+                    }
+                 )*
+            }}
+        }
+
+        t!(f32, f64)
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_durations() {
+        use std::time::Duration;
+
+        let mut rng = ::test::rng(253);
+
+        let v = &[(Duration::new(10, 50000), Duration::new(100, 1234)),
+                  (Duration::new(0, 100), Duration::new(1, 50)),
+                  (Duration::new(0, 0), Duration::new(u64::max_value(), 999_999_999))];
+        for &(low, high) in v.iter() {
+            let my_uniform = Uniform::new(low, high);
+            for _ in 0..1000 {
+                let v = rng.sample(my_uniform);
+                assert!(low <= v && v < high);
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_uniform() {
+        use distributions::uniform::{UniformSampler, UniformFloat, SampleUniform, SampleBorrow};
+        #[derive(Clone, Copy, PartialEq, PartialOrd)]
+        struct MyF32 {
+            x: f32,
+        }
+        #[derive(Clone, Copy, Debug)]
+        struct UniformMyF32 {
+            inner: UniformFloat<f32>,
+        }
+        impl UniformSampler for UniformMyF32 {
+            type X = MyF32;
+            fn new<B1, B2>(low: B1, high: B2) -> Self
+                where B1: SampleBorrow<Self::X> + Sized,
+                      B2: SampleBorrow<Self::X> + Sized
+            {
+                UniformMyF32 {
+                    inner: UniformFloat::<f32>::new(low.borrow().x, high.borrow().x),
+                }
+            }
+            fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+                where B1: SampleBorrow<Self::X> + Sized,
+                      B2: SampleBorrow<Self::X> + Sized
+            {
+                UniformSampler::new(low, high)
+            }
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+                MyF32 { x: self.inner.sample(rng) }
+            }
+        }
+        impl SampleUniform for MyF32 {
+            type Sampler = UniformMyF32;
+        }
+
+        let (low, high) = (MyF32{ x: 17.0f32 }, MyF32{ x: 22.0f32 });
+        let uniform = Uniform::new(low, high);
+        let mut rng = ::test::rng(804);
+        for _ in 0..100 {
+            let x: MyF32 = rng.sample(uniform);
+            assert!(low <= x && x < high);
+        }
+    }
+
+    #[test]
+    fn test_uniform_from_std_range() {
+        let r = Uniform::from(2u32..7);
+        assert_eq!(r.inner.low, 2);
+        assert_eq!(r.inner.range, 5);
+        let r = Uniform::from(2.0f64..7.0);
+        assert_eq!(r.inner.offset, -3.0);
+        assert_eq!(r.inner.scale, 5.0);
+    }
+
+    #[test]
+    #[cfg(feature = "range_inclusive_support")]
+    fn test_uniform_from_std_range_inclusive() {
+        use core::ops::RangeInclusive;
+        let r = Uniform::from(RangeInclusive::new(2u32, 7));
+        assert_eq!(r.inner.low, 2);
+        assert_eq!(r.inner.range, 6);
+        let r = Uniform::from(RangeInclusive::new(2.0f64, 7.0));
+        assert_eq!(r.inner.offset, -3.0);
+        assert_eq!(r.inner.scale, 5.0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_sample_distinct() {
+        let mut rng = ::test::rng(254);
+        // sparse (rejection) and dense (shuffle) cases
+        for &(low, high, k) in [(-500i32, 500, 10), (-5, 5, 10), (3, 20, 12)].iter() {
+            let distr = Uniform::new(low, high);
+            for _ in 0..100 {
+                let mut v = distr.sample_distinct(&mut rng, k);
+                assert_eq!(v.len(), k);
+                assert!(v.iter().all(|&x| low <= x && x < high));
+                v.sort();
+                v.dedup();
+                assert_eq!(v.len(), k);
+            }
+        }
+
+        let distr = Uniform::new_inclusive(::core::u8::MIN, ::core::u8::MAX);
+        let mut v = distr.sample_distinct(&mut rng, 256);
+        v.sort();
+        assert!(v.iter().enumerate().all(|(i, &x)| i == x as usize));
+        assert!(distr.sample_distinct(&mut rng, 0).is_empty());
+
+        // The full range of a type uses the shuffle too.
+        let distr = Uniform::new_inclusive(::core::i8::MIN, ::core::i8::MAX);
+        let mut v = distr.sample_distinct(&mut rng, 200);
+        v.sort();
+        v.dedup();
+        assert_eq!(v.len(), 200);
+    }
+
+    #[should_panic]
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_sample_distinct_too_many() {
+        let mut rng = ::test::rng(255);
+        Uniform::new(0u8, 10).sample_distinct(&mut rng, 11);
+    }
+
+    #[test]
+    fn test_float_sample_mean() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(256);
+        let distr = UniformFloat::<f64>::new(0.0, 1.0);
+        let mean = distr.sample_mean(&mut rng, 1_000_000);
+        assert!((mean - 0.5).abs() < 0.005);
+
+        let distr = UniformFloat::<f32>::new(-2.0, 6.0);
+        let mean = distr.sample_mean(&mut rng, 1_000_000);
+        assert!((mean - 2.0).abs() < 0.04);
+    }
+
+    #[test]
+    fn test_uniform_new_symmetric() {
+        for &n in [1i32, 3, 1000, 1 << 30, ::core::i32::MAX].iter() {
+            let a = Uniform::<i32>::new_symmetric(n);
+            let b = Uniform::new(-n, n);
+            assert_eq!((a.inner.low, a.inner.range, a.inner.zone),
+                       (b.inner.low, b.inner.range, b.inner.zone));
+        }
+        let a = Uniform::<i8>::new_symmetric(100);
+        let b = Uniform::new(-100i8, 100);
+        assert_eq!((a.inner.low, a.inner.range, a.inner.zone),
+                   (b.inner.low, b.inner.range, b.inner.zone));
+
+        // `[-n, n)` is symmetric around -0.5: `x` and `-x - 1` are equally
+        // likely.
+        let mut rng = ::test::rng(257);
+        let distr = Uniform::<i32>::new_symmetric(5);
+        let mut counts = [0i32; 10];
+        for _ in 0..100_000 {
+            counts[(rng.sample(distr) + 5) as usize] += 1;
+        }
+        for i in 0..5 {
+            let (neg, pos) = (counts[4 - i], counts[5 + i]);
+            assert!((neg - pos).abs() < 500);
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_uniform_new_symmetric_zero() {
+        Uniform::<i32>::new_symmetric(0);
+    }
+
+    #[test]
+    fn test_sample_from_words() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+        use rngs::mock::StepRng;
+
+        // For the range [0, 3), only `lo == u32::MAX` is rejected; the low 32
+        // bits of the first word give exactly that.
+        let distr = UniformInt::<u32>::new(0, 3);
+        let words = [0xffff_ffff_5555_5555, 0x8000_0000];
+        assert_eq!(distr.sample_from_words(&mut words.iter().cloned()), Some(1));
+        assert_eq!(distr.sample_from_words(&mut words[..1].iter().cloned()), None);
+        assert_eq!(distr.sample_from_words(&mut [].iter().cloned()), None);
+
+        let mut words = [0u64, 0x8000_0000].iter().cloned();
+        assert_eq!(distr.sample_from_words(&mut words), Some(0));
+        assert_eq!(distr.sample_from_words(&mut words), Some(1));
+        assert_eq!(distr.sample_from_words(&mut words), None);
+
+        // Replaying gives the same result as sampling from an RNG.
+        let distr = UniformInt::<i64>::new(-1000, 1_000_000_007);
+        for &w in [0u64, 12345, 0xdead_beef_0000_0000, ::core::u64::MAX].iter() {
+            let expected = distr.sample(&mut StepRng::new(w, 0));
+            assert_eq!(distr.sample_from_words(&mut ::core::iter::repeat(w)),
+                       Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_char() {
+        let mut rng = ::test::rng(258);
+        let max = ::core::char::from_u32(0x10FFFF).unwrap();
+        let v = &[('a', 'z'), ('\u{D7FF}', '\u{E000}'), ('\u{0000}', max)];
+        for &(low, high) in v.iter() {
+            let my_uniform = Uniform::new(low, high);
+            for _ in 0..1000 {
+                let c: char = rng.sample(my_uniform);
+                assert!(low <= c && c < high);
+            }
+            let my_uniform = Uniform::new_inclusive(low, high);
+            for _ in 0..1000 {
+                let c: char = rng.sample(my_uniform);
+                assert!(low <= c && c <= high);
+            }
+        }
+        // the only two values around the surrogate gap
+        let my_uniform = Uniform::new_inclusive('\u{D7FF}', '\u{E000}');
+        let mut seen = (false, false);
+        for _ in 0..100 {
+            match rng.sample(my_uniform) {
+                '\u{D7FF}' => seen.0 = true,
+                '\u{E000}' => seen.1 = true,
+                c => panic!("unexpected char {:?}", c),
+            }
+        }
+        assert_eq!(seen, (true, true));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_char_printable() {
+        use distributions::Distribution;
+        use distributions::uniform::{UniformChar, PrintableChar};
+        let mut rng = ::test::rng(300);
+        let distr = UniformChar::printable();
+        for _ in 0..10_000 {
+            let c = distr.sample(&mut rng);
+            assert!(!c.is_control());
+            assert!(!('\u{e000}' <= c && c <= '\u{f8ff}'));
+        }
+
+        // Around the C1 control characters and DEL, only '~' and the
+        // no-break space are printable.
+        let distr = PrintableChar::new_inclusive('\u{7e}', '\u{a0}');
+        let mut seen = (false, false);
+        for _ in 0..1000 {
+            match distr.sample(&mut rng) {
+                '~' => seen.0 = true,
+                '\u{a0}' => seen.1 = true,
+                c => panic!("unexpected character {:?}", c),
+            }
+        }
+        assert_eq!(seen, (true, true));
+
+        let distr = PrintableChar::new_inclusive('\u{ad}', '\u{af}');
+        for _ in 0..100 {
+            let c = distr.sample(&mut rng);
+            assert!(c == '\u{ae}' || c == '\u{af}');
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_char_printable_empty() {
+        use distributions::uniform::PrintableChar;
+        PrintableChar::new_inclusive('\u{80}', '\u{9f}');
+    }
+
+    #[test]
+    fn test_char_from_block() {
+        use distributions::uniform::{UniformSampler, UniformChar, UnicodeBlock};
+        let mut rng = ::test::rng(259);
+        let distr = UniformChar::from_block(UnicodeBlock::Hiragana);
+        for _ in 0..1000 {
+            let c = distr.sample(&mut rng);
+            assert!('\u{3040}' <= c && c <= '\u{309F}');
+        }
+    }
+
+    #[test]
+    fn test_is_full_range() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+        use core::{u32, i8};
+        assert!(UniformInt::<u32>::new_inclusive(u32::MIN, u32::MAX).is_full_range());
+        assert!(UniformInt::<i8>::new_inclusive(i8::MIN, i8::MAX).is_full_range());
+        assert!(!UniformInt::<u32>::new(u32::MIN, u32::MAX).is_full_range());
+        assert!(!UniformInt::<i8>::new_inclusive(-5, 5).is_full_range());
+    }
+
+    #[test]
+    fn test_sample_triangle() {
+        use distributions::uniform::sample_triangle;
+        let mut rng = ::test::rng(260);
+        let mut lower_left = 0;
+        for _ in 0..10_000 {
+            let (u, v) = sample_triangle(&mut rng);
+            assert!(u >= 0.0 && v >= 0.0 && u + v <= 1.0);
+            if u < 0.5 && v < 0.5 {
+                lower_left += 1;
+            }
+        }
+        // The square [0, 0.5)^2 covers half the triangle's area.
+        assert!(lower_left > 4800 && lower_left < 5200);
+    }
+
+    #[test]
+    fn test_sample4() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+        let mut rng = ::test::rng(277);
+
+        let distr = UniformInt::<u8>::new(10, 16);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            for &b in distr.sample4(&mut rng).iter() {
+                assert!(10 <= b && b < 16);
+                seen[(b - 10) as usize] = true;
+            }
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        // About half of the bytes are rejected for this range.
+        let distr = UniformInt::<u8>::new_inclusive(0, 128);
+        for _ in 0..1000 {
+            assert!(distr.sample4(&mut rng).iter().all(|&b| b <= 128));
+        }
+
+        let distr = UniformInt::<u8>::new_inclusive(0, 255);
+        assert!(distr.is_full_range());
+        distr.sample4(&mut rng);
+    }
+
+    #[test]
+    fn test_sample_signed_magnitude() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+        use core::i8;
+        let mut rng = ::test::rng(301);
+
+        let distr = UniformInt::<i32>::new(-1000, 1000);
+        for _ in 0..1000 {
+            let (negative, magnitude) = distr.sample_signed_magnitude(&mut rng);
+            assert!(magnitude >= 0);
+            let x = if negative { -magnitude } else { magnitude };
+            assert!(-1000 <= x && x < 1000);
+            assert_eq!(negative, x < 0);
+        }
+
+        let distr = UniformInt::<i8>::new_inclusive(i8::MIN, i8::MIN + 1);
+        for _ in 0..100 {
+            assert_eq!(distr.sample_signed_magnitude(&mut rng), (true, i8::MAX));
+        }
+    }
+
+    #[test]
+    fn test_sample_modular() {
+        use distributions::uniform::UniformInt;
+        let mut rng = ::test::rng(303);
+
+        let mut seen = [false; 16];
+        for _ in 0..1000 {
+            let x = UniformInt::<u32>::sample_modular(&mut rng, 1_000_003, 16);
+            assert!(x < 16);
+            seen[x as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        for _ in 0..1000 {
+            let x = UniformInt::<i8>::sample_modular(&mut rng, -100, 100);
+            assert!(0 <= x && x < 100);
+            let x = UniformInt::<i8>::sample_modular(&mut rng, 127, 127);
+            assert!(0 <= x && x < 127);
+            assert!(UniformInt::<u8>::sample_modular(&mut rng, 250, 251) < 251);
+        }
+
+        // All residues are equally likely.
+        let mut counts = [0; 10];
+        for _ in 0..10_000 {
+            counts[UniformInt::<u32>::sample_modular(&mut rng, 3, 10) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| 900 < c && c < 1100));
+    }
+
+    #[test]
+    fn test_sample_two_distinct() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+        let mut rng = ::test::rng(298);
+
+        let distr = UniformInt::<i16>::new(-2, 3);
+        let mut seen = [[false; 5]; 5];
+        for _ in 0..2000 {
+            let (x, y) = distr.sample_two_distinct(&mut rng);
+            assert!(x != y);
+            assert!(-2 <= x && x < 3 && -2 <= y && y < 3);
+            seen[(x + 2) as usize][(y + 2) as usize] = true;
+        }
+        for i in 0..5 {
+            for j in 0..5 {
+                assert_eq!(seen[i][j], i != j);
+            }
+        }
+
+        let distr = UniformInt::<u8>::new(7, 9);
+        for _ in 0..100 {
+            let (x, y) = distr.sample_two_distinct(&mut rng);
+            assert_eq!(x + y, 15);
+        }
+        let distr = UniformInt::<u64>::new_inclusive(0, ::core::u64::MAX);
+        for _ in 0..100 {
+            let (x, y) = distr.sample_two_distinct(&mut rng);
+            assert!(x != y);
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_sample_two_distinct_single_value() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+        UniformInt::<u32>::new(5, 6).sample_two_distinct(&mut ::test::rng(299));
+    }
+
+    #[test]
+    fn test_sample_batch_stats() {
+        use distributions::uniform::{UniformSampler, UniformInt, RejectionStats};
+        let mut rng = ::test::rng(281);
+
+        // Close to half of the values are rejected for this range.
+        let distr = UniformInt::<u32>::new_inclusive(0, 1 << 31);
+        let mut out = [0u32; 1000];
+        let stats = distr.sample_batch_stats(&mut rng, 900, &mut out);
+        assert!(stats.total_draws >= 900);
+        assert_eq!(stats.rejections, stats.total_draws - 900);
+        assert!(stats.rejections > 300 && stats.max_retries > 0);
+        assert!(out[..900].iter().all(|&x| x <= 1 << 31));
+        assert!(out[900..].iter().all(|&x| x == 0));
+
+        let distr = UniformInt::<u32>::new_inclusive(0, ::core::u32::MAX);
+        let mut out = [0u32; 10];
+        let stats = distr.sample_batch_stats(&mut rng, 10, &mut out);
+        assert_eq!(stats, RejectionStats { total_draws: 10, rejections: 0, max_retries: 0 });
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_sample_disk() {
+        use distributions::uniform::sample_disk;
+        let mut rng = ::test::rng(282);
+        let radius = 3.0;
+        let mut inner = 0;
+        for _ in 0..10_000 {
+            let (x, y) = sample_disk(&mut rng, radius);
+            let r2 = x * x + y * y;
+            assert!(r2 <= radius * radius * (1.0 + 1e-12));
+            if r2 < radius * radius / 4.0 {
+                inner += 1;
+            }
+        }
+        // The disk of half the radius covers a quarter of the area.
+        assert!(inner > 2300 && inner < 2700);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_sample_annulus() {
+        use distributions::uniform::sample_annulus;
+        let mut rng = ::test::rng(322);
+        let (r_inner, r_outer) = (1.0, 2.0);
+        let mut inner_half = 0;
+        for _ in 0..10_000 {
+            let (x, y) = sample_annulus(&mut rng, r_inner, r_outer);
+            let r = (x * x + y * y).sqrt();
+            assert!(r_inner * (1.0 - 1e-12) <= r && r <= r_outer * (1.0 + 1e-12));
+            if r < 1.5 {
+                inner_half += 1;
+            }
+        }
+        // The ring between 1 and 1.5 covers 5/12 of the area.
+        assert!(inner_half > 3950 && inner_half < 4380);
+
+        let (x, y) = sample_annulus(&mut rng, 0.0, 1.0);
+        assert!(x * x + y * y <= 1.0 + 1e-12);
+    }
+
+    #[should_panic]
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_sample_annulus_reversed() {
+        use distributions::uniform::sample_annulus;
+        sample_annulus(&mut ::test::rng(323), 2.0, 1.0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_sample_sphere_surface() {
+        use distributions::uniform::sample_sphere_surface;
+        let mut rng = ::test::rng(328);
+        let mut sum = [0.0; 3];
+        for _ in 0..10_000 {
+            let p = sample_sphere_surface(&mut rng);
+            let norm2 = p[0] * p[0] + p[1] * p[1] + p[2] * p[2];
+            assert!((norm2 - 1.0).abs() < 1e-12);
+            for i in 0..3 {
+                sum[i] += p[i];
+            }
+        }
+        // The points are centered around the origin.
+        for &s in sum.iter() {
+            assert!((s / 10_000.0).abs() < 0.03);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_random_utf8() {
+        use std::str;
+        use distributions::uniform::random_utf8;
+        let mut rng = ::test::rng(289);
+        for max_bytes in 0..40 {
+            let s = random_utf8(&mut rng, max_bytes);
+            assert!(s.len() <= max_bytes);
+            assert!(str::from_utf8(s.as_bytes()).is_ok());
+        }
+        let s = random_utf8(&mut rng, 4000);
+        assert!(s.len() > 3900 && s.len() <= 4000);
+        assert!(s.chars().any(|c| c.len_utf8() < 4));
+    }
+
+    #[test]
+    fn test_dither() {
+        use distributions::uniform::dither;
+        let mut rng = ::test::rng(294);
+        let n = 10_000;
+        let (mut sum, mut central) = (0.0, 0);
+        for _ in 0..n {
+            let d = dither(&mut rng, 0.0);
+            assert!(-1.0 < d && d < 1.0);
+            sum += d;
+            if -0.5 < d && d < 0.5 {
+                central += 1;
+            }
+        }
+        assert!((sum / n as f64).abs() < 0.02);
+        // For the triangular distribution, 3/4 of the mass is in (-0.5, 0.5).
+        assert!(central > 7300 && central < 7700);
+        let x = dither(&mut rng, 100.0);
+        assert!(99.0 <= x && x <= 101.0);
+    }
+
+    #[test]
+    fn test_gen_ratio_bool() {
+        use distributions::uniform::gen_ratio_bool;
+        let mut rng = ::test::rng(296);
+        let mut hits = 0;
+        for _ in 0..2_000_000 {
+            if gen_ratio_bool(&mut rng, 1, 1_000_000) { hits += 1; }
+        }
+        assert!(hits <= 10);
+        let hits = (0..10_000).filter(|_| gen_ratio_bool(&mut rng, 1, 2)).count();
+        assert!(hits > 4800 && hits < 5200);
+        for _ in 0..1000 {
+            assert!(!gen_ratio_bool(&mut rng, 0, 7));
+            assert!(gen_ratio_bool(&mut rng, 7, 7));
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_gen_ratio_bool_invalid() {
+        use distributions::uniform::gen_ratio_bool;
+        gen_ratio_bool(&mut ::test::rng(297), 2, 1);
+    }
+
+    #[test]
+    fn test_try_gen_range() {
+        use distributions::uniform::{try_gen_range, UniformError};
+        let mut rng = ::test::rng(324);
+        for _ in 0..100 {
+            let x: i32 = try_gen_range(&mut rng, -5, 5).unwrap();
+            assert!(-5 <= x && x < 5);
+            let x: f64 = try_gen_range(&mut rng, 1.0, 1.5).unwrap();
+            assert!(1.0 <= x && x < 1.5);
+        }
+        let low = 3u8;
+        assert_eq!(try_gen_range(&mut rng, &low, 4), Ok(3));
+
+        assert_eq!(try_gen_range::<u32, _, _, _>(&mut rng, 10, 5), Err(UniformError::EmptyRange));
+        assert_eq!(try_gen_range::<u32, _, _, _>(&mut rng, 5, 5), Err(UniformError::EmptyRange));
+        assert_eq!(try_gen_range(&mut rng, 0.0, ::core::f64::NAN),
+                   Err(UniformError::EmptyRange));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_sample_bytes_masked() {
+        use distributions::uniform::sample_bytes_masked;
+        let mut rng = ::test::rng(325);
+
+        // A version 4 UUID: the high nibble of byte 6 is 4, the high two bits
+        // of byte 8 are 10.
+        let mut fixed = [0u8; 16];
+        let mut mask = [0xFFu8; 16];
+        fixed[6] = 0x40;
+        mask[6] = 0x0F;
+        fixed[8] = 0x80;
+        mask[8] = 0x3F;
+        let mut varied = [0u8; 16];
+        for _ in 0..100 {
+            let uuid = sample_bytes_masked(&mut rng, &fixed, &mask);
+            assert_eq!(uuid.len(), 16);
+            for i in 0..16 {
+                assert_eq!(uuid[i] & !mask[i], fixed[i]);
+                varied[i] |= uuid[i];
+            }
+        }
+        assert_eq!(&varied[..6], &[0xFF; 6]);
+        assert_eq!(varied[6], 0x4F);
+        assert_eq!(varied[8], 0xBF);
+
+        assert_eq!(sample_bytes_masked(&mut rng, &[1, 2], &[0, 0]), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_sample_ports() {
+        use distributions::uniform::{sample_ephemeral_port, sample_unprivileged_port};
+        let mut rng = ::test::rng(335);
+        let (mut min, mut max) = (::core::u16::MAX, 0);
+        for _ in 0..10_000 {
+            let port = sample_ephemeral_port(&mut rng);
+            assert!(port >= 49152);
+            let port = sample_unprivileged_port(&mut rng);
+            assert!(port >= 1024);
+            min = ::core::cmp::min(min, port);
+            max = ::core::cmp::max(max, port);
+        }
+        assert!(min < 2048 && max > 64512);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_sample_matrix() {
+        use distributions::uniform::sample_matrix;
+        let mut rng = ::test::rng(338);
+        let m = sample_matrix(&mut rng, 3, 5, -2.0, 2.0);
+        assert_eq!(m.len(), 15);
+        assert!(m.iter().all(|&x| -2.0 <= x && x < 2.0));
+        assert!(m.iter().any(|&x| x != m[0]));
+        assert!(sample_matrix(&mut rng, 0, 5, 0.0, 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_sample_subrange() {
+        use distributions::uniform::sample_subrange;
+        let mut rng = ::test::rng(340);
+        let buf = [0u8; 10];
+        let (mut empty, mut full) = (false, false);
+        for _ in 0..1000 {
+            let range = sample_subrange(&mut rng, buf.len());
+            assert!(range.start <= range.end && range.end <= buf.len());
+            empty |= range.start == range.end;
+            full |= range.start == 0 && range.end == 10;
+            let _ = &buf[range];
+        }
+        assert!(empty && full);
+        assert_eq!(sample_subrange(&mut rng, 0), 0..0);
+    }
+
+    #[test]
+    fn test_sample_strided_index() {
+        use distributions::uniform::sample_strided_index;
+        let mut rng = ::test::rng(344);
+        let mut seen = [false; 4];
+        for _ in 0..1000 {
+            let i = sample_strided_index(&mut rng, 270, 64);
+            assert!(i % 64 == 0 && i < 270);
+            seen[i / 64] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert_eq!(sample_strided_index(&mut rng, 5, 5), 0);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_sample_strided_index_zero_stride() {
+        use distributions::uniform::sample_strided_index;
+        sample_strided_index(&mut ::test::rng(345), 10, 0);
+    }
+
+    #[test]
+    fn test_gen_index() {
+        use distributions::uniform::gen_index;
+        let mut rng = ::test::rng(320);
+        let mut seen = [false; 5];
+        for _ in 0..1000 {
+            seen[gen_index(&mut rng, 5)] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert_eq!(gen_index(&mut rng, 1), 0);
+    }
+
+    #[should_panic(expected = "empty")]
+    #[test]
+    fn test_gen_index_empty() {
+        use distributions::uniform::gen_index;
+        gen_index(&mut ::test::rng(321), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_bootstrap_indices() {
+        use distributions::uniform::bootstrap_indices;
+        let mut rng = ::test::rng(329);
+        let indices = bootstrap_indices(&mut rng, 7, 1000);
+        assert_eq!(indices.len(), 1000);
+        assert!(indices.iter().all(|&i| i < 7));
+        // Some indices are repeated.
+        let resample = bootstrap_indices(&mut rng, 100, 100);
+        assert!(resample.iter().enumerate().any(|(i, x)| resample[..i].contains(x)));
+        assert!(bootstrap_indices(&mut rng, 0, 0).is_empty());
+    }
+
+    #[test]
+    fn test_sample_weighted_ordering() {
+        use core::cmp::Ordering;
+        use distributions::uniform::sample_weighted_ordering;
+        let mut rng = ::test::rng(346);
+        let mut counts = [0i32; 3];
+        for _ in 0..10_000 {
+            match sample_weighted_ordering(&mut rng, 2, 7, 1) {
+                Ordering::Less => counts[0] += 1,
+                Ordering::Equal => counts[1] += 1,
+                Ordering::Greater => counts[2] += 1,
+            }
+        }
+        assert!((counts[0] - 2000).abs() < 200);
+        assert!((counts[1] - 7000).abs() < 250);
+        assert!((counts[2] - 1000).abs() < 150);
+
+        assert_eq!(sample_weighted_ordering(&mut rng, 0, 0, 5), Ordering::Greater);
+        assert_eq!(sample_weighted_ordering(&mut rng, ::core::u32::MAX, 0, 0), Ordering::Less);
+    }
+
+    #[test]
+    fn test_weighted_index() {
+        use distributions::uniform::weighted_index;
+        let mut rng = ::test::rng(275);
+        let weights = [0, 1, 0, 3, 6, 0];
+        let mut counts = [0u32; 6];
+        for _ in 0..10_000 {
+            counts[weighted_index(&mut rng, &weights)] += 1;
+        }
+        assert_eq!((counts[0], counts[2], counts[5]), (0, 0, 0));
+        for (&count, &w) in counts.iter().zip(weights.iter()) {
+            let expected = w as i32 * 1000;
+            assert!((count as i32 - expected).abs() < 200);
+        }
+        assert_eq!(weighted_index(&mut rng, &[0, 0, 7]), 2);
+    }
+
+    #[test]
+    fn test_weighted_index_cumulative() {
+        use distributions::uniform::weighted_index_cumulative;
+        let mut rng = ::test::rng(358);
+        // The running totals of the weights `[0, 1, 0, 3, 6, 0]`.
+        let cumulative = [0, 1, 1, 4, 10, 10];
+        let mut counts = [0i32; 6];
+        for _ in 0..10_000 {
+            counts[weighted_index_cumulative(&mut rng, &cumulative)] += 1;
+        }
+        assert_eq!((counts[0], counts[2], counts[5]), (0, 0, 0));
+        for (&count, &expected) in counts.iter().zip([0, 1000, 0, 3000, 6000, 0].iter()) {
+            assert!((count - expected).abs() < 200);
+        }
+        assert_eq!(weighted_index_cumulative(&mut rng, &[0, 0, 7]), 2);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_weighted_index_cumulative_empty() {
+        use distributions::uniform::weighted_index_cumulative;
+        weighted_index_cumulative(&mut ::test::rng(359), &[]);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_weighted_index_zero_total() {
+        use distributions::uniform::weighted_index;
+        weighted_index(&mut ::test::rng(276), &[0, 0]);
+    }
+
+    #[test]
+    fn test_sample_result() {
+        use distributions::uniform::sample_result;
+        let mut rng = ::test::rng(306);
+        let mut oks = 0;
+        for _ in 0..10_000 {
+            let result = sample_result(&mut rng, 0.3,
+                                       |rng| rng.gen_range(0u8, 10),
+                                       |_| "error");
+            match result {
+                Ok(x) => { assert!(x < 10); oks += 1; }
+                Err(e) => assert_eq!(e, "error"),
+            }
+        }
+        assert!(oks > 2800 && oks < 3200);
+
+        let r: Result<(), ()> = sample_result(&mut rng, 1.0, |_| (), |_| ());
+        assert_eq!(r, Ok(()));
+        let r: Result<(), ()> = sample_result(&mut rng, 0.0, |_| (), |_| ());
+        assert_eq!(r, Err(()));
+    }
+
+    #[test]
+    fn test_uniform_ord() {
+        use distributions::Distribution;
+        use distributions::uniform::{Countable, SampleUniform, UniformSampler, UniformOrd};
+
+        #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Id(u16);
+
+        // Relies on the default `advance`.
+        impl Countable for Id {
+            fn successor(&self) -> Option<Self> {
+                self.0.checked_add(1).map(Id)
+            }
+            fn distance(&self, other: &Self) -> u64 {
+                (other.0 - self.0) as u64
+            }
+        }
+
+        impl SampleUniform for Id {
+            type Sampler = UniformOrd<Id>;
+        }
+
+        let mut rng = ::test::rng(307);
+        let distr = Uniform::new(Id(100), Id(110));
+        let mut seen = [false; 10];
+        for _ in 0..1000 {
+            let Id(x) = distr.sample(&mut rng);
+            assert!(100 <= x && x < 110);
+            seen[(x - 100) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert_eq!(rng.gen_range(Id(5), Id(6)), Id(5));
+        assert_eq!(Uniform::new_inclusive(Id(7), Id(7)).sample(&mut rng), Id(7));
+
+        // The surrogate gap is skipped.
+        let distr = UniformOrd::new_inclusive('\u{D7FF}', '\u{E000}');
+        for _ in 0..100 {
+            let c = distr.sample(&mut rng);
+            assert!(c == '\u{D7FF}' || c == '\u{E000}');
+        }
+        assert_eq!('\u{D7FF}'.successor(), Some('\u{E000}'));
+        assert_eq!(::core::char::MAX.successor(), None);
+
+        let distr = UniformOrd::new_inclusive(::core::u64::MIN, ::core::u64::MAX);
+        distr.sample(&mut rng);
+        let distr = UniformOrd::new(-3i8, 3);
+        for _ in 0..100 {
+            let x = distr.sample(&mut rng);
+            assert!(-3 <= x && x < 3);
+        }
+    }
+
+    #[test]
+    fn test_sample_grid() {
+        use distributions::uniform::sample_grid;
+        let mut rng = ::test::rng(308);
+        let lows = [-10, 0, 1 << 40];
+        let highs = [10, 1, (1 << 40) + 3];
+        let mut point = [0i64; 3];
+        for _ in 0..1000 {
+            sample_grid(&mut rng, &lows, &highs, &mut point);
+            for i in 0..3 {
+                assert!(lows[i] <= point[i] && point[i] < highs[i]);
+            }
+        }
+        sample_grid(&mut rng, &[], &[], &mut []);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_sample_grid_empty_range() {
+        use distributions::uniform::sample_grid;
+        sample_grid(&mut ::test::rng(309), &[0, 5], &[1, 5], &mut [0, 0]);
+    }
+
+    #[test]
+    fn test_uniform_int_shifted() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+        let mut rng = ::test::rng(312);
+
+        let distr = UniformInt::<u32>::new(0, 100).shifted(1000);
+        for _ in 0..1000 {
+            let x = distr.sample(&mut rng);
+            assert!(1000 <= x && x < 1100);
+        }
+        let distr = UniformInt::<i8>::new_inclusive(0, 9).shifted(-128);
+        for _ in 0..1000 {
+            let x = distr.sample(&mut rng);
+            assert!(-128 <= x && x <= -119);
+        }
+        assert!(UniformInt::<u8>::new(0, 10).shifted(246).sample(&mut rng) >= 246);
+        // The full range can be shifted arbitrarily.
+        UniformInt::<u16>::new_inclusive(0, ::core::u16::MAX).shifted(1234).sample(&mut rng);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_uniform_int_shifted_overflow() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+        UniformInt::<u8>::new(0, 10).shifted(247);
+    }
+
+    #[test]
+    fn test_accumulate_histogram() {
+        use distributions::uniform::{UniformSampler, UniformInt, UniformFloat};
+        let mut rng = ::test::rng(313);
+
+        let mut bins = [0u64; 10];
+        Uniform::new(-50i32, 50).accumulate_histogram(&mut rng, &mut bins, 100_000);
+        assert_eq!(bins.iter().sum::<u64>(), 100_000);
+        for &count in bins.iter() {
+            assert!(count > 9_500 && count < 10_500);
+        }
+
+        let mut bins = [0u64; 8];
+        Uniform::new(2.0f64, 3.0).accumulate_histogram(&mut rng, &mut bins, 80_000);
+        // Accumulation adds to existing counts.
+        Uniform::new(2.0f64, 3.0).accumulate_histogram(&mut rng, &mut bins, 80_000);
+        assert_eq!(bins.iter().sum::<u64>(), 160_000);
+        for &count in bins.iter() {
+            assert!(count > 19_000 && count < 21_000);
+        }
+
+        let distr = UniformInt::<u8>::new_inclusive(0, 255);
+        assert_eq!(distr.bucket_of(0, 4), 0);
+        assert_eq!(distr.bucket_of(63, 4), 0);
+        assert_eq!(distr.bucket_of(64, 4), 1);
+        assert_eq!(distr.bucket_of(255, 4), 3);
+        let distr = UniformInt::<i32>::new(10, 20);
+        assert_eq!(distr.bucket_of(5, 3), 0);
+        assert_eq!(distr.bucket_of(25, 3), 2);
+        let distr = UniformFloat::<f32>::new(0.0, 1.0);
+        assert_eq!(distr.bucket_of(-1.0, 5), 0);
+        assert_eq!(distr.bucket_of(0.5, 5), 2);
+        assert_eq!(distr.bucket_of(1.0, 5), 4);
+    }
+
+    #[test]
+    fn test_uniform_int_ignores_low_bits() {
+        use {RngCore, Error};
+        use rand_core::impls;
+        use distributions::uniform::{UniformSampler, UniformInt};
+
+        // An adversarial RNG of which the low half of every word is zero. A
+        // sampler using e.g. `x % range` would only output some values.
+        struct LowZeroRng<R>(R);
+        impl<R: RngCore> RngCore for LowZeroRng<R> {
+            fn next_u32(&mut self) -> u32 { self.0.next_u32() & !0xFFFF }
+            fn next_u64(&mut self) -> u64 { self.0.next_u64() & !0xFFFF_FFFF }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                impls::fill_bytes_via_next(self, dest)
+            }
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                Ok(self.fill_bytes(dest))
+            }
+        }
+
+        let mut rng = LowZeroRng(::test::rng(317));
+        let mut counts = [0u32; 6];
+        for _ in 0..60_000 {
+            counts[UniformInt::<u8>::new(0, 6).sample(&mut rng) as usize] += 1;
+        }
+        for &c in counts.iter() {
+            assert!(c > 9_500 && c < 10_500);
+        }
+
+        let mut counts = [0u32; 10];
+        let distr = UniformInt::<u32>::new(0, 10);
+        for _ in 0..100_000 {
+            counts[distr.sample(&mut rng) as usize] += 1;
+        }
+        for &c in counts.iter() {
+            assert!(c > 9_500 && c < 10_500);
+        }
+
+        // Uses 64-bit words.
+        let mut counts = [0u32; 10];
+        let step = 1u64 << 40;
+        let distr = UniformInt::<u64>::new(0, 10 * step);
+        for _ in 0..100_000 {
+            counts[(distr.sample(&mut rng) / step) as usize] += 1;
+        }
+        for &c in counts.iter() {
+            assert!(c > 9_500 && c < 10_500);
+        }
+    }
+
+    #[test]
+    fn test_reflect() {
+        use distributions::uniform::{UniformSampler, UniformInt, UniformFloat};
+        let mut rng = ::test::rng(319);
+
+        let distr = UniformInt::<i32>::new_inclusive(-10, 10);
+        assert_eq!(distr.reflect(5), 5);
+        assert_eq!(distr.reflect(13), 7);
+        assert_eq!(distr.reflect(-13), -7);
+        assert_eq!(distr.reflect(31), -9);
+        assert_eq!(distr.reflect(::core::i32::MIN), -8);
+        for _ in 0..1000 {
+            let x = distr.reflect(rng.gen());
+            assert!(-10 <= x && x <= 10);
+        }
+        let distr = UniformInt::<u8>::new(0, 200);
+        assert_eq!(distr.reflect(255), 143);
+        assert_eq!(UniformInt::<u8>::new(3, 4).reflect(100), 3);
+        assert_eq!(UniformInt::<u8>::new_inclusive(0, 255).reflect(100), 100);
+        let distr = UniformInt::<u64>::new(0, ::core::u64::MAX);
+        assert_eq!(distr.reflect(::core::u64::MAX), ::core::u64::MAX - 2);
+
+        let distr = UniformFloat::<f64>::new_inclusive(1.0, 2.0);
+        assert_eq!(distr.reflect(2.25), 1.75);
+        assert_eq!(distr.reflect(0.5), 1.5);
+        assert_eq!(distr.reflect(3.5), 1.5);
+        for _ in 0..1000 {
+            let x = distr.reflect(rng.gen_range(-1e6, 1e6));
+            assert!(1.0 <= x && x <= 2.0);
+        }
+
+        let distr = UniformFloat::<f32>::new_inclusive(3.0, 3.0);
+        assert_eq!(distr.reflect(3.0), 3.0);
+        assert_eq!(distr.reflect(-7.5), 3.0);
+        assert_eq!(distr.reflect(1e30), 3.0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_uniform_int_sample_prefetched() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+        let mut rng = ::test::rng(327);
+
+        let distr = UniformInt::<u32>::new_inclusive(0, 1 << 31);
+        let values = distr.sample_prefetched(&mut rng, 10_000);
+        assert_eq!(values.len(), 10_000);
+        let mut below = 0;
+        for &x in values.iter() {
+            assert!(x <= 1 << 31);
+            if x < 1 << 30 { below += 1; }
+        }
+        assert!(below > 4_800 && below < 5_200);
+
+        let distr = UniformInt::<i8>::new(-3, 3);
+        let mut counts = [0; 6];
+        for &x in distr.sample_prefetched(&mut rng, 6_000).iter() {
+            counts[(x + 3) as usize] += 1;
+        }
+        for &c in counts.iter() {
+            assert!(c > 850 && c < 1150);
+        }
+        assert!(UniformInt::<u64>::new(0, 10).sample_prefetched(&mut rng, 0).is_empty());
+    }
+
+    #[test]
+    fn test_uniform_int_rejection_counting() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+
+        // For a range of 3, the single value `u32::MAX` is rejected as the low
+        // word of the multiplication; `0x5555_5555 * 3` is exactly that.
+        let distr = UniformInt::<u32>::new(0, 3);
+        let mut rng = CountingRng::new(&[0x5555_5555, 0xAAAA_AAAB, 0]);
+        assert_eq!(distr.sample(&mut rng), 2);
+        assert_eq!(rng.used, 2);
+        assert_eq!(distr.sample(&mut rng), 0);
+        assert_eq!(rng.used, 3);
+
+        // `u64` ranges use the exact zone too: for a range of 3 only the low
+        // word `u64::MAX` is rejected.
+        let distr = UniformInt::<u64>::new(10, 13);
+        let mut rng = CountingRng::new(&[0x5555_5555_5555_5555, 0x4000_0000]);
+        assert_eq!(distr.sample(&mut rng), 10);
+        assert_eq!(rng.used, 2);
+        let mut rng = CountingRng::new(&[0x4000_0000, 0xAAAA_AAAA_AAAA_AAAB]);
+        assert_eq!(distr.sample(&mut rng), 10);
+        assert_eq!(distr.sample(&mut rng), 12);
+        assert_eq!(rng.used, 2);
+
+        // `sample_with_u32` draws `u32`s, rejecting only the low word
+        // `u32::MAX`.
+        let mut rng = CountingRng::new(&[0x5555_5555, 0x4000_0000, 0xAAAA_AAAB]);
+        assert_eq!(distr.sample_with_u32(&mut rng), 10);
+        assert_eq!(rng.used, 2);
+        assert_eq!(distr.sample_with_u32(&mut rng), 12);
+        assert_eq!(rng.used, 3);
+
+        // Small types sample a `u32` with a zone grown to fit it; only the
+        // low words `0xFFFF_FFFC` and above are rejected.
+        let distr = UniformInt::<i8>::new(-3, 3);
+        let mut rng = CountingRng::new(&[0x2AAA_AAAA, 0x2AAA_AAAB]);
+        assert_eq!(distr.sample(&mut rng), -2);
+        assert_eq!(rng.used, 2);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_piecewise_uniform() {
+        use distributions::Distribution;
+        use distributions::uniform::PiecewiseUniform;
+        let mut rng = ::test::rng(336);
+
+        let distr = PiecewiseUniform::new(&[(Uniform::new(0, 10), 1.0),
+                                            (Uniform::new(20, 25), 2.0),
+                                            (Uniform::new(100, 200), 7.0)]);
+        let mut counts = [0; 3];
+        for _ in 0..10_000 {
+            let x = distr.sample(&mut rng);
+            let segment = if x < 10 {
+                0
+            } else if 20 <= x && x < 25 {
+                1
+            } else {
+                assert!(100 <= x && x < 200);
+                2
+            };
+            counts[segment] += 1;
+        }
+        assert!(counts[0] > 850 && counts[0] < 1150);
+        assert!(counts[1] > 1800 && counts[1] < 2200);
+        assert!(counts[2] > 6700 && counts[2] < 7300);
+
+        let distr = PiecewiseUniform::new(&[(Uniform::new(0.0, 1.0), 0.5)]);
+        let x = distr.sample(&mut rng);
+        assert!(0.0 <= x && x < 1.0);
+    }
+
+    #[should_panic]
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_piecewise_uniform_zero_weight() {
+        use distributions::uniform::PiecewiseUniform;
+        PiecewiseUniform::new(&[(Uniform::new(0, 10), 1.0), (Uniform::new(20, 30), 0.0)]);
+    }
+
+    #[test]
+    #[cfg(feature = "maybe_uninit_support")]
+    fn test_sample_into_uninit() {
+        use core::mem::MaybeUninit;
+        use distributions::uniform::{UniformSampler, UniformInt, UniformFloat};
+        let mut rng = ::test::rng(337);
+
+        let mut buf: [MaybeUninit<i16>; 64] = unsafe { MaybeUninit::uninit().assume_init() };
+        UniformInt::<i16>::new(-100, 100).sample_into_uninit(&mut rng, &mut buf);
+        for x in buf.iter() {
+            let x = unsafe { x.assume_init() };
+            assert!(-100 <= x && x < 100);
+        }
+
+        let mut buf: [MaybeUninit<f64>; 64] = unsafe { MaybeUninit::uninit().assume_init() };
+        UniformFloat::<f64>::new(1.0, 2.0).sample_into_uninit(&mut rng, &mut buf);
+        for x in buf.iter() {
+            let x = unsafe { x.assume_init() };
+            assert!(1.0 <= x && x < 2.0);
+        }
+    }
+
+    #[test]
+    fn test_leapfrog() {
+        let distr = Uniform::new(0u32, 1000);
+
+        let mut full = [0u32; 30];
+        for (x, y) in full.iter_mut().zip(distr.iter(&mut ::test::rng(339))) {
+            *x = y;
+        }
+        // Workers use RNGs with the same seed.
+        let k = 3;
+        for i in 0..k {
+            let mut rng = ::test::rng(339);
+            for (j, x) in distr.leapfrog(&mut rng, k, i).take(10).enumerate() {
+                assert_eq!(x, full[i + j * k]);
+            }
+        }
+        let mut rng = ::test::rng(339);
+        assert!(distr.leapfrog(&mut rng, 1, 0).take(30).eq(full.iter().cloned()));
+    }
+
+    #[test]
+    fn test_pdf() {
+        use distributions::uniform::{UniformSampler, UniformInt, UniformFloat};
+
+        assert_eq!(UniformInt::<u32>::new(10, 14).pdf(), 0.25);
+        assert_eq!(UniformInt::<i8>::new_inclusive(-5, 4).pdf() * 10.0, 1.0);
+        assert_eq!(UniformInt::<u8>::new_inclusive(0, 255).pdf(), 1.0 / 256.0);
+        assert_eq!(UniformInt::<i64>::new_inclusive(7, 7).pdf(), 1.0);
+
+        let distr = UniformFloat::<f64>::new(-1.5, 2.5);
+        assert_eq!(distr.pdf() * 4.0, 1.0);
+        let distr = UniformFloat::<f32>::new(0.0, 0.1);
+        assert!((distr.pdf() * 0.1 - 1.0).abs() < 1e-6);
+        let distr = UniformFloat::<f32>::new(-3e38, 3e38);
+        assert!((distr.pdf() * 6e38 - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_uniform_int_sample_neighbor() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+        let mut rng = ::test::rng(348);
+
+        let distr = UniformInt::<i32>::new(-100, 100);
+        let mut current = 37;
+        for _ in 0..1000 {
+            let next = distr.sample_neighbor(&mut rng, current);
+            assert!(-100 <= next && next < 100);
+            assert_eq!((next ^ current).count_ones(), 1);
+            current = next;
+        }
+
+        let distr = UniformInt::<u8>::new(200, 201);
+        assert_eq!(distr.sample_neighbor(&mut rng, 200), 200);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_english_letter_frequency() {
+        use distributions::uniform::UniformChar;
+        use distributions::Distribution;
+        let mut rng = ::test::rng(349);
+
+        let distr = UniformChar::english_letter_frequency();
+        let mut counts = [0u32; 26];
+        for _ in 0..10000 {
+            let c = distr.sample(&mut rng);
+            assert!('a' <= c && c <= 'z');
+            counts[(c as u8 - b'a') as usize] += 1;
+        }
+        assert!(counts[(b'e' - b'a') as usize] > counts[(b'z' - b'a') as usize]);
+        assert!(counts[(b'e' - b'a') as usize] > 1000);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_sample_bytes_below() {
+        use distributions::uniform::sample_bytes_below;
+        let mut rng = ::test::rng(350);
+
+        let uppers: &[&[u8]] = &[&[1], &[0, 0, 5, 0], &[0x80, 0, 0], &[0xff; 20], &[3, 1]];
+        for upper in uppers.iter() {
+            for _ in 0..500 {
+                let x = sample_bytes_below(&mut rng, upper);
+                assert_eq!(x.len(), upper.len());
+                assert!(&x[..] < *upper);
+            }
+        }
+
+        // Values are spread over the whole range, not just the top byte.
+        let mut seen_high = [false; 3];
+        for _ in 0..1000 {
+            let x = sample_bytes_below(&mut rng, &[3, 1]);
+            if x[0] < 3 {
+                seen_high[x[0] as usize] = true;
+            } else {
+                assert_eq!(x, [3, 0]);
+            }
+        }
+        assert_eq!(seen_high, [true, true, true]);
+    }
+
+    #[test]
+    fn test_uniform_signed_unbiased() {
+        use distributions::{Distribution, Uniform};
+        let mut rng = ::test::rng(351);
+
+        // A range crossing zero, to catch sign-extension bugs in the
+        // rejection zone.
+        let distr = Uniform::new(-3i32, 4);
+        const N: usize = 70000;
+        let mut counts = [0usize; 7];
+        for _ in 0..N {
+            let x = distr.sample(&mut rng);
+            assert!(-3 <= x && x < 4);
+            counts[(x + 3) as usize] += 1;
+        }
+
+        let expected = N as f64 / 7.0;
+        let chi2 = counts.iter().fold(0.0, |acc, &c| {
+            let d = c as f64 - expected;
+            acc + d * d / expected
+        });
+        // Critical value for 6 degrees of freedom at p = 0.001.
+        assert!(chi2 < 22.458, "chi-squared statistic {} too large", chi2);
+
+        let negative: usize = counts[..3].iter().sum();
+        let positive: usize = counts[4..].iter().sum();
+        let diff = if negative > positive { negative - positive } else { positive - negative };
+        assert!(diff < 1500, "unbalanced: {} negative, {} positive", negative, positive);
+    }
+
+    #[test]
+    fn test_retry_histogram() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+        let mut rng = ::test::rng(261);
+
+        let distr = UniformInt::<u32>::new(0, 100);
+        let histogram = retry_histogram(100_000, |draws| {
+            distr.sample_counting(&mut rng, draws);
+        });
+        assert_eq!(histogram.iter().sum::<u64>(), 100_000);
+        assert!(histogram[0] > 99_990);
+
+        // Almost half of all values are rejected for this range.
+        let distr = UniformInt::<u32>::new_inclusive(0, 1 << 31);
+        let histogram = retry_histogram(100_000, |draws| {
+            distr.sample_counting(&mut rng, draws);
+        });
+        assert_eq!(histogram.iter().sum::<u64>(), 100_000);
+        assert!(histogram[0] > 45_000 && histogram[0] < 55_000);
+        assert!(histogram[1] > 20_000 && histogram[1] < 30_000);
+        assert!(histogram[7] > 0);
+
+        let distr = UniformInt::<u8>::new_inclusive(0, 255);
+        let histogram = retry_histogram(1000, |draws| {
+            distr.sample_counting(&mut rng, draws);
+        });
+        assert_eq!(histogram[0], 1000);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_boxed() {
+        use std::thread;
+        use distributions::{Distribution, DynDistribution, Standard};
+
+        let distrs: Vec<Box<DynDistribution<u32> + Send + Sync>> = vec![
+            Uniform::new(10u32, 20).boxed(),
+            Uniform::new_inclusive(0u32, 3).boxed(),
+            Box::new(Standard),
+        ];
+        let wide: Box<DynDistribution<i64> + Send + Sync> =
+            Uniform::new(-1000i64, 1000).boxed();
+
+        let handle = thread::spawn(move || {
+            let mut rng = ::test::rng(266);
+            for _ in 0..100 {
+                let x = distrs[0].sample(&mut rng);
+                assert!(10 <= x && x < 20);
+                let y = distrs[1].sample(&mut rng);
+                assert!(y <= 3);
+                distrs[2].sample(&mut rng);
+                let z = wide.sample(&mut rng);
+                assert!(-1000 <= z && z < 1000);
+            }
+        });
+        handle.join().unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_durations_from_range() {
+        use std::time::Duration;
+        let mut rng = ::test::rng(304);
+        let (low, high) = (Duration::new(0, 0), Duration::new(1, 0));
+        let distr = Uniform::from(low..high);
+        for _ in 0..1000 {
+            let d = rng.sample(distr);
+            assert!(low <= d && d < high);
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "range_inclusive_support"))]
+    fn test_durations_from_range_inclusive() {
+        use core::ops::RangeInclusive;
+        use std::time::Duration;
+        let mut rng = ::test::rng(357);
+
+        let (low, high) = (Duration::new(0, 0), Duration::new(1, 0));
+        let distr = Uniform::from(RangeInclusive::new(low, high));
+        for _ in 0..1000 {
+            let d = rng.sample(distr);
+            assert!(low <= d && d <= high);
+        }
+
+        // The upper bound is included.
+        let distr = Uniform::from(RangeInclusive::new(low, Duration::new(0, 3)));
+        let mut seen = [false; 4];
+        for _ in 0..100 {
+            seen[rng.sample(distr).subsec_nanos() as usize] = true;
+        }
+        assert_eq!(seen, [true; 4]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_durations_single_value() {
+        use std::time::Duration;
+        let mut rng = ::test::rng(280);
+        let low = Duration::new(0, 5);
+        let exclusive = Uniform::new(low, Duration::new(0, 6));
+        let inclusive = Uniform::new_inclusive(low, low);
+        for _ in 0..100 {
+            assert_eq!(rng.sample(exclusive), low);
+            assert_eq!(rng.sample(inclusive), low);
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_durations_empty() {
+        use std::time::Duration;
+        Uniform::new(Duration::new(0, 5), Duration::new(0, 5));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_durations_sample_single_inclusive() {
+        use std::time::Duration;
+        use distributions::uniform::UniformDuration;
+        let mut rng = ::test::rng(268);
+
+        let low = Duration::new(1, 999_999_998);
+        let high = Duration::new(2, 1);
+        let mut hit_high = false;
+        for _ in 0..1000 {
+            let d = UniformDuration::sample_single_inclusive(low, high, &mut rng);
+            assert!(low <= d && d <= high);
+            hit_high |= d == high;
+        }
+        assert!(hit_high);
+
+        let d = Duration::new(5, 0);
+        assert_eq!(UniformDuration::sample_single_inclusive(d, d, &mut rng), d);
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "i128_support"))]
+    fn test_durations_exact128() {
+        use std::time::Duration;
+        use distributions::uniform::UniformDurationMode;
+        let mut rng = ::test::rng(284);
+
+        // Ten centuries do not fit in `u64` nanoseconds.
+        let low = Duration::new(1_000, 5);
+        let high = low + Duration::new(1000 * 365 * 24 * 3600, 0);
+        let distr = Uniform::new(low, high);
+        match distr.inner.mode {
+            UniformDurationMode::Exact128 { .. } => {}
+            _ => panic!("expected the 128-bit mode"),
+        }
+        let mut subsec_nonzero = false;
+        for _ in 0..1000 {
+            let d = rng.sample(distr);
+            assert!(low <= d && d < high);
+            subsec_nonzero |= d.subsec_nanos() != 5;
+        }
+        assert!(subsec_nonzero);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_durations_millis() {
+        use std::time::Duration;
+        use distributions::uniform::{UniformSampler, UniformDuration};
+        let mut rng = ::test::rng(290);
+        let distr = UniformDuration::new_millis(1500, 2500);
+        let mut seen_next_sec = false;
+        for _ in 0..1000 {
+            let d = distr.sample(&mut rng);
+            assert_eq!(d.subsec_nanos() % 1_000_000, 0);
+            assert!(Duration::from_millis(1500) <= d && d < Duration::from_millis(2500));
+            seen_next_sec |= d.as_secs() == 2;
+        }
+        assert!(seen_next_sec);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_durations_sample_scaled() {
+        use std::time::Duration;
+        use distributions::uniform::sample_scaled;
+        let mut rng = ::test::rng(314);
+
+        let base = Duration::new(1, 500_000_000);
+        for _ in 0..1000 {
+            let d = sample_scaled(&mut rng, base, 0.8, 1.2);
+            assert!(Duration::new(1, 200_000_000) <= d && d <= Duration::new(1, 800_000_000));
+        }
+        let base = Duration::from_millis(10);
+        for _ in 0..1000 {
+            let d = sample_scaled(&mut rng, base, 0.5, 0.6);
+            assert!(Duration::from_millis(5) <= d && d <= Duration::from_millis(6));
+        }
+
+        let max = Duration::new(::core::u64::MAX, 999_999_999);
+        assert_eq!(sample_scaled(&mut rng, max, 2.0, 3.0), max);
+        assert_eq!(sample_scaled(&mut rng, Duration::new(0, 0), 2.0, 3.0), Duration::new(0, 0));
+    }
+
+    #[should_panic]
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_durations_sample_scaled_negative() {
+        use std::time::Duration;
+        use distributions::uniform::sample_scaled;
+        sample_scaled(&mut ::test::rng(315), Duration::new(1, 0), -0.5, 1.0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_durations_sample_exp() {
+        use std::time::Duration;
+        use distributions::uniform::sample_exp_duration;
+        let mut rng = ::test::rng(318);
+
+        let mean = Duration::from_millis(250);
+        let n = 10_000;
+        let mut total = Duration::new(0, 0);
+        let mut below_mean = 0;
+        for _ in 0..n {
+            let d = sample_exp_duration(&mut rng, mean);
+            total += d;
+            if d < mean { below_mean += 1; }
+        }
+        let avg_ms = (total.as_secs() * 1000 + total.subsec_nanos() as u64 / 1_000_000) / n;
+        assert!(avg_ms > 240 && avg_ms < 260);
+        // `1 - 1/e` of the samples are below the mean.
+        assert!(below_mean > 6_100 && below_mean < 6_550);
+
+        assert_eq!(sample_exp_duration(&mut rng, Duration::new(0, 0)), Duration::new(0, 0));
+        let max = Duration::new(::core::u64::MAX, 999_999_999);
+        sample_exp_duration(&mut rng, max);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_durations_ticks() {
+        use std::time::Duration;
+        use distributions::uniform::{UniformSampler, UniformDuration};
+        let mut rng = ::test::rng(347);
+
+        let tick = Duration::new(0, 16_666_667);
+        let distr = UniformDuration::new_ticks(10, 20, tick);
+        let mut seen = [false; 10];
+        for _ in 0..1000 {
+            let d = distr.sample(&mut rng);
+            let nanos = d.as_secs() * 1_000_000_000 + d.subsec_nanos() as u64;
+            assert_eq!(nanos % 16_666_667, 0);
+            let ticks = nanos / 16_666_667;
+            assert!(10 <= ticks && ticks < 20);
+            seen[ticks as usize - 10] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        let distr = UniformDuration::new_ticks(3_000_000_000, 3_000_000_001,
+                                               Duration::new(2, 999_999_999));
+        assert_eq!(distr.sample(&mut rng), Duration::new(8_999_999_997, 0));
+        let max = Duration::new(::core::u64::MAX, 999_999_999);
+        let distr = UniformDuration::new_ticks(::core::u64::MAX - 1, ::core::u64::MAX,
+                                               Duration::new(0, 2));
+        assert_eq!(distr.sample(&mut rng), Duration::new(36_893_488_147, 419_103_228));
+        let distr = UniformDuration::new_ticks(2, 3, max);
+        assert_eq!(distr.sample(&mut rng), max);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_durations_log() {
+        use std::time::Duration;
+        use distributions::uniform::{UniformSampler, UniformDuration};
+        let mut rng = ::test::rng(262);
+
+        let (low, high) = (Duration::from_millis(1), Duration::from_secs(10));
+        let distr = UniformDuration::new_log(low, high);
+        // one bucket per order of magnitude
+        let mut counts = [0; 4];
+        for _ in 0..10_000 {
+            let d = distr.sample(&mut rng);
+            assert!(low <= d && d < high);
+            let bucket = if d < Duration::from_millis(10) { 0 }
+                else if d < Duration::from_millis(100) { 1 }
+                else if d < Duration::from_secs(1) { 2 }
+                else { 3 };
+            counts[bucket] += 1;
+        }
+        for &count in counts.iter() {
+            assert!(count > 2300 && count < 2700);
+        }
+
+        // Samples close to a whole second, where the sub-second remainder
+        // may round to a value just below zero.
+        let (low, high) = (Duration::new(1, 999_999_990), Duration::new(2, 10));
+        let distr = UniformDuration::new_log(low, high);
+        for _ in 0..1000 {
+            let d = distr.sample(&mut rng);
+            assert!(low <= d && d < high);
+        }
+
+        // The logarithms of both bounds round to the same `f64`.
+        let low = Duration::from_secs(1_000_000);
+        let high = low + Duration::new(0, 1);
+        let distr = UniformDuration::new_log(low, high);
+        for _ in 0..100 {
+            assert_eq!(distr.sample(&mut rng), low);
+        }
+    }
+
+    #[test]
+    fn test_quantile() {
+        use distributions::uniform::{UniformSampler, UniformInt, UniformFloat};
+
+        let distr = UniformInt::<i32>::new(-5, 5);
+        assert_eq!(distr.quantile(0.0), -5);
+        assert_eq!(distr.quantile(0.5), 0);
+        assert_eq!(distr.quantile(0.99), 4);
+        let distr = UniformInt::<u64>::new_inclusive(0, ::core::u64::MAX);
+        assert_eq!(distr.quantile(0.0), 0);
+        assert_eq!(distr.quantile(0.5), 1 << 63);
+        assert!(distr.quantile(1.0 - 1e-16) > ::core::u64::MAX - 4096);
+        let distr = UniformInt::<u64>::new(0, ::core::u64::MAX - 1);
+        assert!(distr.quantile(1.0 - 1e-16) <= ::core::u64::MAX - 2);
+
+        let distr = UniformFloat::<f64>::new(2.0, 4.0);
+        assert_eq!(distr.quantile(0.0), 2.0);
+        assert_eq!(distr.quantile(0.25), 2.5);
+
+        let int = UniformInt::<u8>::new_inclusive(10, 200);
+        let float = UniformFloat::<f32>::new(-1.0, 3.0);
+        let mut q = 0.0;
+        let (mut prev_int, mut prev_float) = (int.quantile(0.0), float.quantile(0.0));
+        while q < 1.0 {
+            let (i, f) = (int.quantile(q), float.quantile(q));
+            assert!(prev_int <= i && prev_float <= f);
+            assert!(i <= 200 && f < 3.0);
+            prev_int = i;
+            prev_float = f;
+            q += 0.001;
+        }
+    }
+
+    #[test]
+    fn test_float_sample_stratified() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(288);
+        let distr = UniformFloat::<f64>::new(-1.0, 3.0);
+        let mut out = [0.0; 8];
+        for _ in 0..100 {
+            distr.sample_stratified(&mut rng, 8, &mut out);
+            for (i, &x) in out.iter().enumerate() {
+                let low = -1.0 + i as f64 * 0.5;
+                assert!(low <= x && x <= low + 0.5);
+            }
+        }
+        distr.sample_stratified(&mut rng, 0, &mut []);
+    }
+
+    #[test]
+    fn test_cdf() {
+        use distributions::uniform::{UniformSampler, UniformInt, UniformFloat};
+
+        let distr = UniformInt::<i32>::new(-5, 5);
+        assert_eq!(distr.cdf(-100), 0.0);
+        assert_eq!(distr.cdf(-5), 0.1);
+        assert_eq!(distr.cdf(-1), 0.5);
+        assert_eq!(distr.cdf(4), 1.0);
+        assert_eq!(distr.cdf(100), 1.0);
+        let distr = UniformInt::<u8>::new_inclusive(0, 255);
+        assert_eq!(distr.cdf(0), 1.0 / 256.0);
+        assert_eq!(distr.cdf(127), 0.5);
+        assert_eq!(distr.cdf(255), 1.0);
+
+        let distr = UniformFloat::<f64>::new(2.0, 4.0);
+        assert_eq!(distr.cdf(2.0), 0.0);
+        assert_eq!(distr.cdf(3.0), 0.5);
+        assert!(distr.cdf(4.0 - 1e-12) > 0.999);
+        assert_eq!(distr.cdf(4.0), 1.0);
+        assert_eq!(distr.cdf(-1.0), 0.0);
+
+        // A single point has all its mass at `low == high`.
+        let point = UniformFloat::<f32>::new_inclusive(1.5, 1.5);
+        assert_eq!(point.cdf(1.0), 0.0);
+        assert_eq!(point.cdf(1.5), 1.0);
+        assert_eq!(point.cdf(2.0), 1.0);
+
+        // The quantiles of values are found again.
+        for &q in [0.0, 0.1, 0.25, 0.7].iter() {
+            assert!((distr.cdf(distr.quantile(q)) - q).abs() < 1e-12);
+        }
+        let mut rng = ::test::rng(302);
+        let distr = UniformFloat::<f32>::new(-1.0, 1.0);
+        let mut below_half = 0;
+        for _ in 0..10_000 {
+            if distr.cdf(distr.sample(&mut rng)) < 0.5 {
+                below_half += 1;
+            }
+        }
+        assert!(below_half > 4800 && below_half < 5200);
+    }
+
+    #[test]
+    fn test_sample_biased() {
+        let mut rng = ::test::rng(286);
+        let distr = Uniform::new(0u32, 100);
+        let mut preferred = 0;
+        for _ in 0..10_000 {
+            let x = distr.sample_biased(&mut rng, 42, 0.3);
+            assert!(x < 100);
+            if x == 42 { preferred += 1; }
+        }
+        // 30% plus 1% of the uniform samples
+        assert!(preferred > 2900 && preferred < 3300);
+
+        let distr = Uniform::new_inclusive(-1.0f64, 1.0);
+        for _ in 0..100 {
+            assert_eq!(distr.sample_biased(&mut rng, 1.0, 1.0), 1.0);
+            assert!(distr.sample_biased(&mut rng, 0.5, 0.0) != 0.5);
+        }
+        let full = Uniform::new_inclusive(::core::i8::MIN, ::core::i8::MAX);
+        assert_eq!(full.sample_biased(&mut rng, -128, 1.0), -128);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_sample_biased_out_of_range() {
+        let mut rng = ::test::rng(287);
+        Uniform::new(0i32, 10).sample_biased(&mut rng, 10, 0.5);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_no_repeat() {
+        use distributions::uniform::NoRepeat;
+        let mut rng = ::test::rng(295);
+        let mut sampler = NoRepeat::<u8>::new(Uniform::new(0, 10), 3);
+        let mut last = [sampler.sample(&mut rng); 3];
+        let mut seen = [false; 10];
+        for i in 0..1000 {
+            let x = sampler.sample(&mut rng);
+            assert!(x < 10);
+            assert!(!last.contains(&x));
+            last[i % 3] = x;
+            seen[x as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        // With `k` one less than the range size, the values cycle.
+        let mut sampler = NoRepeat::<i32>::new(Uniform::new_inclusive(-1, 1), 2);
+        let first = [sampler.sample(&mut rng), sampler.sample(&mut rng),
+                     sampler.sample(&mut rng)];
+        for _ in 0..10 {
+            for &x in first.iter() {
+                assert_eq!(sampler.sample(&mut rng), x);
+            }
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_no_repeat_k_too_large() {
+        use distributions::uniform::NoRepeat;
+        NoRepeat::<u8>::new(Uniform::new(0, 10), 10);
+    }
+
+    #[test]
+    fn test_filter() {
+        let mut rng = ::test::rng(291);
+        let evens = Uniform::new(0, 100).filter(|x| x % 2 == 0);
+        let mut seen = [false; 50];
+        for _ in 0..2000 {
+            let x: i32 = rng.sample(&evens);
+            assert!(0 <= x && x < 100 && x % 2 == 0);
+            seen[(x / 2) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn test_uniform_iter() {
+        use distributions::uniform::UniformIter;
+
+        struct Dice<'a, R: Rng + 'a> {
+            rolls: UniformIter<'a, u8, R>,
+        }
+
+        let mut rng = ::test::rng(285);
+        let distr = Uniform::new_inclusive(1u8, 6);
+        let mut dice = Dice { rolls: distr.iter(&mut rng) };
+        let mut sixes = 0;
+        for x in dice.rolls.by_ref().take(600).filter(|&x| x == 6) {
+            assert_eq!(x, 6);
+            sixes += 1;
+        }
+        assert!(sixes > 60 && sixes < 140);
+        let mut pairs = 0;
+        for (a, b) in dice.rolls.by_ref().zip(1..7).take(6) {
+            assert!(1 <= a && a <= 6);
+            assert!(1 <= b && b <= 6);
+            pairs += 1;
+        }
+        assert_eq!(pairs, 6);
+    }
+
+    #[test]
+    fn test_sample_seeded() {
+        let distr = Uniform::new(0u64, 1 << 40);
+        assert_eq!(distr.sample_seeded(1), distr.sample_seeded(1));
+        assert_eq!(distr.sample_seeded(12345), distr.sample_seeded(12345));
+        assert!(distr.sample_seeded(1) != distr.sample_seeded(2));
+        let x = Uniform::new(-1.0, 1.0).sample_seeded(7);
+        assert!(-1.0 <= x && x <= 1.0);
+    }
+
+    #[test]
+    fn test_stream() {
+        use prng::XorShiftRng;
+        let distr = Uniform::new(0u32, 1_000_000);
+        let sample10 = |base_seed, task_id| {
+            let mut values = [0u32; 10];
+            let stream = distr.stream::<XorShiftRng>(base_seed, task_id);
+            for (v, x) in values.iter_mut().zip(stream) {
+                *v = x;
+            }
+            values
+        };
+        assert_eq!(sample10(42, 0), sample10(42, 0));
+        assert_eq!(sample10(42, 7), sample10(42, 7));
+        assert!(sample10(42, 0) != sample10(42, 1));
+        assert!(sample10(42, 1) != sample10(43, 1));
+        assert!(sample10(0, 0).iter().all(|&x| x < 1_000_000));
+    }
+
+    #[test]
+    fn test_sample_after_skip() {
+        let distr = Uniform::new(0u64, 1 << 40);
+        let a = distr.sample_after_skip(&mut ::test::rng(263), 0);
+        let b = distr.sample_after_skip(&mut ::test::rng(263), 3);
+        assert!(a != b);
+
+        let mut rng = ::test::rng(263);
+        for _ in 0..3 {
+            rng.gen::<u64>();
+        }
+        assert_eq!(rng.sample(distr), b);
+    }
+
+    #[test]
+    fn test_as_inclusive_exclusive() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+        let mut rng = ::test::rng(264);
+
+        let extremes = |distr: UniformInt<i8>, rng: &mut ::test::TestRng<_>| {
+            let mut min = ::core::i8::MAX;
+            let mut max = ::core::i8::MIN;
+            for _ in 0..1000 {
+                let x = distr.sample(rng);
+                min = ::core::cmp::min(min, x);
+                max = ::core::cmp::max(max, x);
+            }
+            (min, max)
+        };
+
+        let distr = UniformInt::<i8>::new(-5, 5);
+        assert_eq!(extremes(distr, &mut rng), (-5, 4));
+        assert_eq!(extremes(distr.as_inclusive(), &mut rng), (-5, 5));
+        let distr = UniformInt::<i8>::new_inclusive(-5, 5);
+        assert_eq!(extremes(distr.as_exclusive(), &mut rng), (-5, 4));
+
+        let distr = UniformInt::<i8>::new_inclusive(0, 127);
+        let inclusive = distr.as_inclusive();
+        assert_eq!((inclusive.low, inclusive.range), (distr.low, distr.range));
+        let full = UniformInt::<i8>::new_inclusive(-128, 127).as_exclusive();
+        assert_eq!(extremes(full, &mut rng), (-128, 126));
+    }
+
+    #[test]
+    fn test_floats_subnormal_range() {
+        use core::{f32, f64};
+        use distributions::uniform::UniformUlps;
+        let mut rng = ::test::rng(265);
+
+        // [0, 8 ULPs): every value should be hit, `high` never.
+        let high = f64::from_bits(8);
+        let distr = UniformUlps::<f64>::new(0.0, high);
+        let mut seen = [false; 8];
+        for _ in 0..1000 {
+            let x: f64 = rng.sample(distr);
+            assert!(0.0 <= x && x < high);
+            seen[x.to_bits() as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        // a range crossing zero, with inclusive `high`
+        let (low, high) = (-f32::from_bits(3), f32::from_bits(3));
+        let distr = UniformUlps::<f32>::new_inclusive(low, high);
+        let mut seen = [false; 7];
+        for _ in 0..1000 {
+            let x: f32 = rng.sample(distr);
+            assert!(low <= x && x <= high);
+            seen[(x / f32::from_bits(1)) as i32 as usize + 3] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        let (low, high) = (f64::MIN_POSITIVE, 2.0 * f64::MIN_POSITIVE);
+        let mut values = [0.0f64; 100];
+        for x in values.iter_mut() {
+            *x = rng.gen_range(low, high);
+            assert!(low <= *x && *x < high);
+        }
+        values.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!(values.windows(2).filter(|w| w[0] != w[1]).count() > 90);
+    }
+
+    #[test]
+    fn test_float_sample_raw() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(279);
+
+        let distr = UniformFloat::<f32>::new(-3.0, 5.0);
+        let mut max = 0;
+        for _ in 0..1000 {
+            let (x, raw) = distr.sample_raw(&mut rng);
+            assert!(-3.0 <= x && x <= 5.0);
+            assert!(raw < 1 << 23);
+            max = ::core::cmp::max(max, raw);
+        }
+        assert!(max >= 1 << 22);
+
+        let distr = UniformFloat::<f64>::new(1.0, 2.0);
+        let mut max = 0;
+        for _ in 0..1000 {
+            let (x, raw) = distr.sample_raw(&mut rng);
+            assert!(raw < 1 << 52);
+            assert_eq!(x, 1.0 + raw as f64 / (1u64 << 52) as f64);
+            max = ::core::cmp::max(max, raw);
+        }
+        assert!(max >= 1 << 51);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_float_sample_sorted() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(283);
+
+        let distr = UniformFloat::<f64>::new(-10.0, 10.0);
+        let values = distr.sample_sorted(&mut rng, 1000);
+        assert_eq!(values.len(), 1000);
+        assert!(values.iter().all(|&x| -10.0 <= x && x <= 10.0));
+        assert!(values.windows(2).all(|w| w[0] <= w[1]));
+        // The median should be close to the center of the range.
+        assert!(values[500].abs() < 1.0);
+
+        let distr = UniformFloat::<f32>::new(0.0, 1e-40);
+        let values = distr.sample_sorted(&mut rng, 100);
+        assert!(values.windows(2).all(|w| w[0] <= w[1]));
+        assert!(distr.sample_sorted(&mut rng, 0).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_float_sample_rounded_decimals() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(305);
+
+        let distr = UniformFloat::<f64>::new(-1.5, 2.5);
+        for &decimals in [0, 1, 2, 5].iter() {
+            let p = 10f64.powi(decimals);
+            for _ in 0..1000 {
+                let x = distr.sample_rounded_decimals(&mut rng, decimals as u32);
+                assert!(-1.5 <= x && x <= 2.5);
+                assert!(((x * p).round() - x * p).abs() < 1e-6);
+            }
+        }
+
+        // The nearest value with one decimal would be 0.2 or 0.3.
+        let distr = UniformFloat::<f32>::new(0.23, 0.27);
+        for _ in 0..100 {
+            let x = distr.sample_rounded_decimals(&mut rng, 1);
+            assert!(0.23 <= x && x <= 0.27);
+        }
+
+        // More decimals than the type can represent leave samples unchanged.
+        for &decimals in [39, 400, ::core::u32::MAX].iter() {
+            let x = distr.sample_rounded_decimals(&mut rng, decimals);
+            assert!(0.23 <= x && x <= 0.27);
+        }
+        let distr = UniformFloat::<f64>::new(1e300, 1e301);
+        for &decimals in [10, 309, 400].iter() {
+            let x = distr.sample_rounded_decimals(&mut rng, decimals);
+            assert!(1e300 <= x && x <= 1e301);
+        }
+        let distr = UniformFloat::<f64>::new_inclusive(0.0, 0.0);
+        assert_eq!(distr.sample_rounded_decimals(&mut rng, 400), 0.0);
+    }
+
+    #[test]
+    fn test_bytes_per_sample() {
+        use distributions::uniform::{UniformSampler, UniformInt, UniformFloat};
+
+        // Almost no draws are rejected.
+        let distr = UniformInt::<u32>::new(0, 10);
+        assert_eq!(distr.max_bytes_per_sample(), 4);
+        let expected = distr.expected_bytes_per_sample();
+        assert!(4.0 <= expected && expected < 4.001);
+
+        // Almost half of the draws are rejected.
+        let distr = UniformInt::<u32>::new_inclusive(0, 1 << 31);
+        assert_eq!(distr.max_bytes_per_sample(), 4);
+        let expected = distr.expected_bytes_per_sample();
+        assert!(7.99 < expected && expected <= 8.0);
+
+        let distr = UniformInt::<u64>::new(0, 1 << 40);
+        assert_eq!(distr.max_bytes_per_sample(), 8);
+        assert_eq!(distr.expected_bytes_per_sample(), 8.0);
+        let distr = UniformInt::<u8>::new_inclusive(0, 255);
+        assert_eq!(distr.max_bytes_per_sample(), 4);
+        assert_eq!(distr.expected_bytes_per_sample(), 4.0);
+
+        let distr = UniformFloat::<f32>::new(1.0, 2.0);
+        assert_eq!(distr.max_bytes_per_sample(), 4);
+        assert_eq!(distr.expected_bytes_per_sample(), 4.0);
+        let distr = UniformFloat::<f64>::new(1.0, 2.0);
+        assert_eq!(distr.max_bytes_per_sample(), 8);
+        assert_eq!(distr.expected_bytes_per_sample(), 8.0);
+
+        // Small `u64` ranges take a full word, and (almost) never reject one.
+        let distr = UniformInt::<u64>::new(10, 13);
+        assert_eq!(distr.max_bytes_per_sample(), 8);
+        assert!(distr.expected_bytes_per_sample() < 8.0 + 1e-9);
+
+        // A single point takes no draws at all.
+        let distr = UniformFloat::<f64>::new_inclusive(5.0, 5.0);
+        assert_eq!(distr.max_bytes_per_sample(), 0);
+        assert_eq!(distr.expected_bytes_per_sample(), 0.0);
+        let mut rng = CountingRng::new(&[]);
+        assert_eq!(distr.sample(&mut rng), 5.0);
+        assert_eq!(distr.sample_with_uniform(&mut rng), (5.0, 0.0));
+        assert_eq!(rng.used, 0);
+    }
+
+    #[test]
+    fn test_float_sample_conditioned() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(310);
+
+        let distr = UniformFloat::<f64>::new(-5.0, 5.0);
+        for _ in 0..1000 {
+            let x = distr.sample_conditioned(&mut rng, 2.0, 100.0);
+            assert!(2.0 <= x && x < 5.0);
+            let x = distr.sample_conditioned(&mut rng, -0.5, 0.5);
+            assert!(-0.5 <= x && x < 0.5);
+        }
+        let distr = UniformFloat::<f32>::new(0.0, 1.0);
+        for _ in 0..1000 {
+            let x = distr.sample_conditioned(&mut rng, -1.0, 0.25);
+            assert!(0.0 <= x && x < 0.25);
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_float_sample_conditioned_disjoint() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let distr = UniformFloat::<f64>::new(0.0, 1.0);
+        distr.sample_conditioned(&mut ::test::rng(311), 1.0, 2.0);
+    }
+
+    #[test]
+    fn test_float_new_levels() {
+        use distributions::Distribution;
+        let mut rng = ::test::rng(316);
+
+        let distr = Uniform::<f64>::new_levels(-6.0, 3.0, 4);
+        let levels = [-6.0, -3.0, 0.0, 3.0];
+        let mut seen = [false; 4];
+        for _ in 0..1000 {
+            let x = distr.sample(&mut rng);
+            let i = levels.iter().position(|&l| l == x).unwrap();
+            seen[i] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        let distr = Uniform::<f32>::new_levels(0.1, 0.7, 7);
+        let mut seen_high = false;
+        for _ in 0..1000 {
+            let x = distr.sample(&mut rng);
+            assert!(0.1 <= x && x <= 0.7);
+            seen_high |= x == 0.7;
+        }
+        assert!(seen_high);
+    }
+
+    #[test]
+    fn test_float_sample_biased_high_low() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(326);
+
+        let distr = UniformFloat::<f64>::new(10.0, 16.0);
+        let (mut sum_high, mut sum_low) = (0.0, 0.0);
+        for _ in 0..10_000 {
+            let high = distr.sample_biased_high(&mut rng);
+            let low = distr.sample_biased_low(&mut rng);
+            assert!(10.0 <= high && high < 16.0);
+            assert!(10.0 <= low && low < 16.0);
+            sum_high += high;
+            sum_low += low;
+        }
+        let (mean_high, mean_low) = (sum_high / 10_000.0, sum_low / 10_000.0);
+        assert!(mean_high > 13.0);
+        assert!((mean_high - 14.0).abs() < 0.1);
+        assert!((mean_low - 12.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_float_sample_diff() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(330);
+
+        let distr = UniformFloat::<f64>::new(5.0, 7.0);
+        let mut sum = 0.0;
+        let mut near_zero = 0;
+        for _ in 0..10_000 {
+            let d = distr.sample_diff(&mut rng);
+            assert!(-2.0 < d && d < 2.0);
+            sum += d;
+            if -1.0 < d && d < 1.0 { near_zero += 1; }
+        }
+        assert!((sum / 10_000.0).abs() < 0.05);
+        // Three quarters of the mass is within half the bound.
+        assert!(near_zero > 7_300 && near_zero < 7_700);
+    }
+
+    #[test]
+    fn test_float_phase() {
+        use core::f64::consts::PI;
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(331);
+        let distr = UniformFloat::<f64>::phase();
+        let mut upper_half = 0;
+        for _ in 0..1000 {
+            let x = distr.sample(&mut rng);
+            assert!(0.0 <= x && x < 2.0 * PI);
+            if x >= PI { upper_half += 1; }
+        }
+        assert!(upper_half > 430 && upper_half < 570);
+    }
 
-    #[should_panic]
     #[test]
-    fn test_uniform_bad_limits_equal_int() {
-        Uniform::new(10, 10);
+    fn test_float_sample_near() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(332);
+
+        let distr = UniformFloat::<f64>::new(0.0, 100.0);
+        for _ in 0..1000 {
+            let center = distr.sample(&mut rng);
+            let x = distr.sample_near(&mut rng, center, 5.0);
+            assert!(center - 5.0 <= x && x < center + 5.0);
+            assert!(0.0 <= x && x < 100.0);
+        }
+        // The window is clamped to the range.
+        for _ in 0..1000 {
+            let x = distr.sample_near(&mut rng, 99.0, 10.0);
+            assert!(89.0 <= x && x < 100.0);
+        }
+        let distr = UniformFloat::<f32>::new(-1.0, 1.0);
+        for _ in 0..1000 {
+            let x = distr.sample_near(&mut rng, -1.5, 1.0);
+            assert!(-1.0 <= x && x < -0.5);
+        }
     }
 
     #[should_panic]
     #[test]
-    fn test_uniform_bad_limits_equal_float() {
-        Uniform::new(10., 10.);
+    fn test_float_sample_near_outside() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let distr = UniformFloat::<f64>::new(0.0, 1.0);
+        distr.sample_near(&mut ::test::rng(333), 5.0, 1.0);
     }
 
     #[test]
-    fn test_uniform_good_limits_equal_int() {
-        let mut rng = ::test::rng(804);
-        let dist = Uniform::new_inclusive(10, 10);
-        for _ in 0..20 {
-            assert_eq!(rng.sample(dist), 10);
+    fn test_float_sample_with_uniform() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(334);
+
+        let distr = UniformFloat::<f64>::new(-3.0, 5.0);
+        for _ in 0..1000 {
+            let (x, q) = distr.sample_with_uniform(&mut rng);
+            assert!(0.0 <= q && q < 1.0);
+            assert!((-3.0 + q * 8.0 - x).abs() < 1e-12);
+        }
+        let distr = UniformFloat::<f32>::new(10.0, 11.0);
+        for _ in 0..1000 {
+            let (x, q) = distr.sample_with_uniform(&mut rng);
+            assert!(0.0 <= q && q < 1.0);
+            assert!((10.0 + q as f32 - x).abs() < 1e-5);
+        }
+        // Sampled in ULPs.
+        let distr = UniformFloat::<f64>::new(0.0, 1e-310);
+        for _ in 0..1000 {
+            let (x, q) = distr.sample_with_uniform(&mut rng);
+            assert!(0.0 <= q && q < 1.0);
+            assert!((q * 1e-310 - x).abs() < 1e-320);
         }
     }
 
     #[test]
-    fn test_uniform_good_limits_equal_float() {
-        let mut rng = ::test::rng(805);
-        let dist = Uniform::new_inclusive(10., 10.);
-        for _ in 0..20 {
-            assert_eq!(rng.sample(dist), 10.);
+    #[cfg(feature = "std")]
+    fn test_float_new_with_bits() {
+        use std::collections::HashSet;
+        use distributions::Distribution;
+        use distributions::uniform::UniformFloat;
+        let mut rng = ::test::rng(341);
+
+        let distr = UniformFloat::<f64>::new_with_bits(-1.0, 3.0, 8);
+        let mut seen = HashSet::new();
+        for _ in 0..10_000 {
+            let x = distr.sample(&mut rng);
+            assert!(-1.0 <= x && x < 3.0);
+            seen.insert(x.to_bits());
         }
+        assert!(seen.len() > 200 && seen.len() <= 256);
+
+        let distr = UniformFloat::<f32>::new_with_bits(0.0, 1.0, 0);
+        assert_eq!(distr.sample(&mut rng), 0.0);
+        let distr = UniformFloat::<f32>::new_with_bits(0.0, 1.0, 23);
+        assert!(distr.sample(&mut rng) < 1.0);
     }
 
     #[should_panic]
     #[test]
-    fn test_uniform_bad_limits_flipped_int() {
-        Uniform::new(10, 5);
+    fn test_float_new_with_bits_too_many() {
+        use distributions::uniform::UniformFloat;
+        UniformFloat::<f32>::new_with_bits(0.0, 1.0, 24);
+    }
+
+    #[test]
+    fn test_float_sample_eased() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(342);
+
+        let distr = UniformFloat::<f64>::new(10.0, 20.0);
+        let mut lower_half = 0;
+        for _ in 0..10_000 {
+            let x = distr.sample_eased(&mut rng, |q| q * q);
+            assert!(10.0 <= x && x < 20.0);
+            if x < 15.0 { lower_half += 1; }
+        }
+        // `q * q < 0.5` for `q < 1 / sqrt(2)`.
+        assert!(lower_half > 6_900 && lower_half < 7_250);
+
+        let distr = UniformFloat::<f32>::new(0.0, 1.0);
+        let mut sum = 0.0;
+        for _ in 0..10_000 {
+            sum += distr.sample_eased(&mut rng, |q| q);
+        }
+        assert!((sum / 10_000.0 - 0.5).abs() < 0.01);
     }
 
     #[should_panic]
     #[test]
-    fn test_uniform_bad_limits_flipped_float() {
-        Uniform::new(10., 5.);
+    fn test_float_sample_eased_out_of_range() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let distr = UniformFloat::<f64>::new(0.0, 1.0);
+        distr.sample_eased(&mut ::test::rng(343), |q| q + 1.0);
     }
 
     #[test]
-    fn test_integers() {
-        let mut rng = ::test::rng(251);
-        macro_rules! t {
-            ($($ty:ident),*) => {{
-                $(
-                   let v: &[($ty, $ty)] = &[(0, 10),
-                                            (10, 127),
-                                            (::core::$ty::MIN, ::core::$ty::MAX)];
-                   for &(low, high) in v.iter() {
-                        let my_uniform = Uniform::new(low, high);
-                        for _ in 0..1000 {
-                            let v: $ty = rng.sample(my_uniform);
-                            assert!(low <= v && v < high);
-                        }
+    #[cfg(feature = "std")]
+    fn test_float_sample_min_of() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(353);
 
-                        let my_uniform = Uniform::new_inclusive(low, high);
-                        for _ in 0..1000 {
-                            let v: $ty = rng.sample(my_uniform);
-                            assert!(low <= v && v <= high);
-                        }
+        let distr = UniformFloat::<f64>::new(2.0, 7.0);
+        for &n in [1u32, 4, 20].iter() {
+            let mut sum = 0.0;
+            for _ in 0..10000 {
+                let x = distr.sample_min_of(&mut rng, n);
+                assert!(2.0 <= x && x < 7.0);
+                sum += x;
+            }
+            let expected = 2.0 + 5.0 / (n + 1) as f64;
+            let mean = sum / 10000.0;
+            assert!((mean - expected).abs() < 0.05, "n = {}: mean {}", n, mean);
+        }
+    }
 
-                        let my_uniform = Uniform::new(&low, high);
-                        for _ in 0..1000 {
-                            let v: $ty = rng.sample(my_uniform);
-                            assert!(low <= v && v < high);
-                        }
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_float_sample_max_of() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(354);
 
-                        let my_uniform = Uniform::new_inclusive(&low, &high);
-                        for _ in 0..1000 {
-                            let v: $ty = rng.sample(my_uniform);
-                            assert!(low <= v && v <= high);
-                        }
+        let distr = UniformFloat::<f64>::new(2.0, 7.0);
+        let mut last_mean = 2.0;
+        for &n in [1u32, 4, 20, 100].iter() {
+            let mut sum = 0.0;
+            for _ in 0..10000 {
+                let x = distr.sample_max_of(&mut rng, n);
+                assert!(2.0 <= x && x < 7.0);
+                sum += x;
+            }
+            let mean = sum / 10000.0;
+            let expected = 2.0 + 5.0 * n as f64 / (n + 1) as f64;
+            assert!((mean - expected).abs() < 0.05, "n = {}: mean {}", n, mean);
+            assert!(mean > last_mean);
+            last_mean = mean;
+        }
+        assert!(last_mean > 6.9);
 
-                        for _ in 0..1000 {
-                            let v: $ty = rng.gen_range(low, high);
-                            assert!(low <= v && v < high);
-                        }
-                    }
-                 )*
-            }}
+        for _ in 0..100 {
+            let x = distr.sample_max_of(&mut rng, ::core::u32::MAX);
+            assert!(2.0 <= x && x < 7.0);
         }
-        t!(i8, i16, i32, i64, isize,
-           u8, u16, u32, u64, usize);
-        #[cfg(feature = "i128_support")]
-        t!(i128, u128)
     }
 
     #[test]
-    fn test_floats() {
-        let mut rng = ::test::rng(252);
-        macro_rules! t {
-            ($($ty:ty),*) => {{
-                $(
-                   let v: &[($ty, $ty)] = &[(0.0, 100.0),
-                                            (-1e35, -1e25),
-                                            (1e-35, 1e-25),
-                                            (-1e35, 1e35)];
-                   for &(low, high) in v.iter() {
-                        let my_uniform = Uniform::new(low, high);
-                        for _ in 0..1000 {
-                            let v: $ty = rng.sample(my_uniform);
-                            assert!(low <= v && v < high);
-                        }
-                    }
-                 )*
-            }}
+    fn test_float_sample_correlated() {
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(274);
+        let distr = UniformFloat::<f64>::new(0.0, 1.0);
+
+        for _ in 0..100 {
+            let (x, y) = distr.sample_correlated(&mut rng, 1.0);
+            assert_eq!(x, y);
         }
 
-        t!(f32, f64)
+        // For uniform values, the rank correlation is close to the
+        // correlation coefficient.
+        let mut correlation = |rho| {
+            let n = 10_000;
+            let mut sum = 0.0;
+            for _ in 0..n {
+                let (x, y) = distr.sample_correlated(&mut rng, rho);
+                sum += (x - 0.5) * (y - 0.5);
+            }
+            12.0 * sum / n as f64
+        };
+        let c = correlation(0.0);
+        assert!(-0.05 < c && c < 0.05);
+        let c = correlation(0.5);
+        assert!(0.45 < c && c < 0.55);
     }
 
     #[test]
-    #[cfg(feature = "std")]
-    fn test_durations() {
-        use std::time::Duration;
-
-        let mut rng = ::test::rng(253);
+    fn test_floats_no_negative_zero() {
+        let mut rng = ::test::rng(273);
+        let is_neg_zero = |x: f64| x == 0.0 && x.is_sign_negative();
 
-        let v = &[(Duration::new(10, 50000), Duration::new(100, 1234)),
-                  (Duration::new(0, 100), Duration::new(1, 50)),
-                  (Duration::new(0, 0), Duration::new(u64::max_value(), 999_999_999))];
-        for &(low, high) in v.iter() {
-            let my_uniform = Uniform::new(low, high);
+        // Ranges with a zero-crossing, where samples are often exactly zero.
+        let distrs = [
+            Uniform::new(-1.0, 1.0),
+            Uniform::new(-1.0, 0.5),
+            Uniform::new_inclusive(-0.0, 0.0),
+            Uniform::new(-0.0, 1.0),
+            Uniform::new(-1e-310, 1e-310),
+        ];
+        for distr in distrs.iter() {
             for _ in 0..1000 {
-                let v = rng.sample(my_uniform);
-                assert!(low <= v && v < high);
+                assert!(!is_neg_zero(rng.sample(distr)));
             }
         }
+        for _ in 0..1000 {
+            assert!(!is_neg_zero(rng.gen_range(-2.0, 2.0)));
+            let x: f32 = rng.gen_range(-1e-40, 1e-40);
+            assert!(!(x == 0.0 && x.is_sign_negative()));
+        }
     }
 
     #[test]
-    fn test_custom_uniform() {
-        use distributions::uniform::{UniformSampler, UniformFloat, SampleUniform, SampleBorrow};
-        #[derive(Clone, Copy, PartialEq, PartialOrd)]
-        struct MyF32 {
-            x: f32,
+    fn test_float_degenerate_inclusive() {
+        struct PanicRng;
+        impl RngCore for PanicRng {
+            fn next_u32(&mut self) -> u32 { panic!("RNG used") }
+            fn next_u64(&mut self) -> u64 { panic!("RNG used") }
+            fn fill_bytes(&mut self, _: &mut [u8]) { panic!("RNG used") }
+            fn try_fill_bytes(&mut self, _: &mut [u8]) -> Result<(), Error> {
+                panic!("RNG used")
+            }
         }
-        #[derive(Clone, Copy, Debug)]
-        struct UniformMyF32 {
-            inner: UniformFloat<f32>,
+
+        let mut rng = PanicRng;
+        assert_eq!(rng.sample(Uniform::new_inclusive(5.0, 5.0)), 5.0);
+        assert!(rng.sample(Uniform::new_inclusive(-0.0f32, -0.0)).is_sign_positive());
+        assert_eq!(rng.sample(Uniform::new_inclusive(1e-310, 1e-310)), 1e-310);
+        assert_eq!(rng.sample(Uniform::new_inclusive(::core::f64::MAX, ::core::f64::MAX)),
+                   ::core::f64::MAX);
+
+        // Closed ranges with `low < high` are sampled as before.
+        let mut rng = ::test::rng(355);
+        for _ in 0..100 {
+            let x = rng.sample(Uniform::new_inclusive(1.0, 2.0));
+            assert!(1.0 <= x && x <= 2.0);
         }
-        impl UniformSampler for UniformMyF32 {
-            type X = MyF32;
-            fn new<B1, B2>(low: B1, high: B2) -> Self
-                where B1: SampleBorrow<Self::X> + Sized,
-                      B2: SampleBorrow<Self::X> + Sized
-            {
-                UniformMyF32 {
-                    inner: UniformFloat::<f32>::new(low.borrow().x, high.borrow().x),
-                }
-            }
-            fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
-                where B1: SampleBorrow<Self::X> + Sized,
-                      B2: SampleBorrow<Self::X> + Sized
-            {
-                UniformSampler::new(low, high)
-            }
-            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
-                MyF32 { x: self.inner.sample(rng) }
+    }
+
+    #[test]
+    fn test_float_sample_checked() {
+        use core::f64::EPSILON;
+        use distributions::uniform::{UniformSampler, UniformFloat};
+        let mut rng = ::test::rng(267);
+
+        // With a range of a single ULP above 1.0, roughly half of the
+        // samples round up to `high`.
+        let distr = UniformFloat::<f64>::new(1.0, 1.0 + EPSILON);
+        let mut hits = 0;
+        for _ in 0..1000 {
+            let (x, hit_high) = distr.sample_checked(&mut rng);
+            assert_eq!(hit_high, x == 1.0 + EPSILON);
+            if hit_high { hits += 1; }
+        }
+        assert!(hits > 0 && hits < 1000);
+
+        let distr = UniformFloat::<f32>::new(0.0, 1.0);
+        for _ in 0..1000 {
+            let (x, hit_high) = distr.sample_checked(&mut rng);
+            assert!(x < 1.0 && !hit_high);
+        }
+    }
+
+    #[test]
+    fn test_uniform_u64_small_range() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+        let mut rng = ::test::rng(272);
+        let distr = UniformInt::<u64>::new(0, 1000);
+        let mut buckets = [0u32; 10];
+        for _ in 0..100_000 {
+            let x = distr.sample_with_u32(&mut rng);
+            assert!(x < 1000);
+            buckets[(x / 100) as usize] += 1;
+        }
+        for &count in buckets.iter() {
+            assert!(9_500 < count && count < 10_500);
+        }
+
+        // Ranges wider than 32 bits fall back to `sample`.
+        let distr = UniformInt::<i64>::new(-3_000_000_000, 3_000_000_000);
+        for _ in 0..1000 {
+            let x = distr.sample_with_u32(&mut rng);
+            assert!(-3_000_000_000 <= x && x < 3_000_000_000);
+        }
+        let distr = UniformInt::<i64>::new(-3_000_000_000, -1_000_000_000);
+        for _ in 0..1000 {
+            let x = distr.sample_with_u32(&mut rng);
+            assert!(-3_000_000_000 <= x && x < -1_000_000_000);
+        }
+    }
+
+    #[test]
+    fn test_new_fixed() {
+        let mut rng = ::test::rng(292);
+        // Q16.16
+        let distr = Uniform::new_fixed(-1.5, 2.25, 16);
+        let (mut min, mut max) = (::core::i32::MAX, ::core::i32::MIN);
+        for _ in 0..10_000 {
+            let x = rng.sample(distr);
+            let value = x as f64 / 65536.0;
+            assert!(-1.5 <= value && value < 2.25);
+            min = ::core::cmp::min(min, x);
+            max = ::core::cmp::max(max, x);
+        }
+        assert!(min < -98_000 && max > 147_000);
+
+        // Bounds between fixed-point values
+        let distr = Uniform::new_fixed(0.1, 0.9, 1);
+        for _ in 0..100 {
+            assert_eq!(rng.sample(distr), 1);
+        }
+        // The full Q0.31 range
+        let distr = Uniform::new_fixed(-1.0, 1.0, 31);
+        rng.sample(distr);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_new_fixed_empty() {
+        Uniform::new_fixed(0.1, 0.2, 1);
+    }
+
+    #[test]
+    fn test_from_bounds() {
+        use core::ops::Bound::{Included, Excluded, Unbounded};
+        use core::{i8, u8};
+        let mut rng = ::test::rng(293);
+
+        fn check<R: Rng>(distr: Uniform<i8>, low: i8, high: i8, rng: &mut R) {
+            let (mut min, mut max) = (i8::MAX, i8::MIN);
+            for _ in 0..2000 {
+                let x = rng.sample(distr);
+                min = ::core::cmp::min(min, x);
+                max = ::core::cmp::max(max, x);
             }
+            assert_eq!((min, max), (low, high));
         }
-        impl SampleUniform for MyF32 {
-            type Sampler = UniformMyF32;
+        check(Uniform::<i8>::from_bounds(Included(-3), Included(5)), -3, 5, &mut rng);
+        check(Uniform::<i8>::from_bounds(Included(-3), Excluded(5)), -3, 4, &mut rng);
+        check(Uniform::<i8>::from_bounds(Excluded(0), Included(5)), 1, 5, &mut rng);
+        check(Uniform::<i8>::from_bounds(Excluded(0), Excluded(5)), 1, 4, &mut rng);
+        check(Uniform::<i8>::from_bounds(Unbounded, Excluded(-100)), i8::MIN, -101, &mut rng);
+        check(Uniform::<i8>::from_bounds(Excluded(100), Unbounded), 101, i8::MAX, &mut rng);
+        check(Uniform::<i8>::from_bounds(Unbounded, Unbounded), i8::MIN, i8::MAX, &mut rng);
+
+        let distr = Uniform::<u8>::from_bounds(Excluded(u8::MAX - 1), Unbounded);
+        assert_eq!(rng.sample(distr), u8::MAX);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_from_bounds_empty() {
+        use core::ops::Bound::Excluded;
+        Uniform::<u32>::from_bounds(Excluded(5), Excluded(6));
+    }
+
+    #[test]
+    fn test_int_new_centered() {
+        use core::{i32, i8};
+        let mut rng = ::test::rng(270);
+
+        let distr = Uniform::<i32>::new_centered(100, 3);
+        let mut seen = [false; 7];
+        for _ in 0..1000 {
+            let x = rng.sample(distr);
+            assert!(97 <= x && x <= 103);
+            seen[(x - 97) as usize] = true;
         }
+        assert!(seen.iter().all(|&s| s));
 
-        let (low, high) = (MyF32{ x: 17.0f32 }, MyF32{ x: 22.0f32 });
-        let uniform = Uniform::new(low, high);
-        let mut rng = ::test::rng(804);
+        // The range may reach the limits of the type.
+        let distr = Uniform::<i32>::new_centered(i32::MAX - 5, 5);
+        let mut hit_max = false;
+        for _ in 0..1000 {
+            let x = rng.sample(distr);
+            assert!(i32::MAX - 10 <= x);
+            hit_max |= x == i32::MAX;
+        }
+        assert!(hit_max);
+
+        let distr = Uniform::<i8>::new_centered(0, i8::MAX);
         for _ in 0..100 {
-            let x: MyF32 = rng.sample(uniform);
-            assert!(low <= x && x < high);
+            assert!(rng.sample(distr) >= -i8::MAX);
         }
+        assert_eq!(rng.sample(Uniform::<u8>::new_centered(7, 0)), 7);
     }
 
+    #[should_panic]
     #[test]
-    fn test_uniform_from_std_range() {
-        let r = Uniform::from(2u32..7);
-        assert_eq!(r.inner.low, 2);
-        assert_eq!(r.inner.range, 5);
-        let r = Uniform::from(2.0f64..7.0);
-        assert_eq!(r.inner.offset, -3.0);
-        assert_eq!(r.inner.scale, 5.0);
+    fn test_int_new_centered_negative() {
+        Uniform::<i32>::new_centered(0, -1);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_int_new_centered_overflow() {
+        Uniform::<i32>::new_centered(::core::i32::MAX - 2, 5);
+    }
+
+    #[test]
+    #[cfg(feature = "nonzero_support")]
+    fn test_nonzero() {
+        use core::num::NonZeroUsize;
+        let mut rng = ::test::rng(271);
+        let one = NonZeroUsize::new(1).unwrap();
+        let len = NonZeroUsize::new(5).unwrap();
+        let distr = Uniform::new(one, len);
+        let mut seen = [false; 5];
+        for _ in 0..1000 {
+            let i = rng.sample(distr).get();
+            assert!(1 <= i && i < 5);
+            seen[i] = true;
+        }
+        assert!(!seen[0] && seen[1..].iter().all(|&s| s));
+
+        let d = rng.gen_range(len, NonZeroUsize::new(6).unwrap());
+        assert_eq!(d, len);
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "core_net_support"))]
+    fn test_ipv4() {
+        #[cfg(feature = "std")]
+        use std::net::Ipv4Addr;
+        #[cfg(not(feature = "std"))]
+        use core::net::Ipv4Addr;
+        let mut rng = ::test::rng(356);
+
+        let low = Ipv4Addr::new(192, 168, 0, 0);
+        let high = Ipv4Addr::new(192, 168, 3, 255);
+        let distr = Uniform::new_inclusive(low, high);
+        let mut seen = [false; 4];
+        for _ in 0..1000 {
+            let addr = rng.sample(distr);
+            assert!(low <= addr && addr <= high);
+            seen[addr.octets()[2] as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        let distr = Uniform::new(Ipv4Addr::new(10, 0, 0, 1), Ipv4Addr::new(10, 0, 0, 2));
+        assert_eq!(rng.sample(distr), Ipv4Addr::new(10, 0, 0, 1));
+    }
+
+    #[test]
+    fn test_float_new_centered() {
+        let mut rng = ::test::rng(269);
+        let distr = Uniform::<f64>::new_centered(-2.5, 0.5);
+        for _ in 0..1000 {
+            let x = rng.sample(distr);
+            assert!(-3.0 <= x && x < -2.0);
+        }
+        let distr = Uniform::<f32>::new_centered(100.0, 1e-3);
+        for _ in 0..1000 {
+            let x = rng.sample(distr);
+            assert!(100.0 - 1e-3 <= x && x <= 100.0 + 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_float_new_percent() {
+        let mut rng = ::test::rng(278);
+        let distr = Uniform::<f64>::new_percent(25.0, 75.0);
+        for _ in 0..1000 {
+            let x = rng.sample(distr);
+            assert!(0.25 <= x && x < 0.75);
+        }
+        let distr = Uniform::<f32>::new_percent(0.0, 100.0);
+        for _ in 0..1000 {
+            let x = rng.sample(distr);
+            assert!(0.0 <= x && x < 1.0);
+        }
+    }
+
+    #[test]
+    fn test_float_new_degrees() {
+        use core::f64::consts::PI;
+        let mut rng = ::test::rng(352);
+        let distr = Uniform::<f64>::new_degrees(0.0, 180.0);
+        for _ in 0..1000 {
+            let x = rng.sample(distr);
+            assert!(0.0 <= x && x < PI);
+        }
+        let distr = Uniform::<f64>::new_degrees(-90.0, 90.0);
+        for _ in 0..1000 {
+            let x = rng.sample(distr);
+            assert!(-PI / 2.0 <= x && x < PI / 2.0);
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_float_new_percent_out_of_bounds() {
+        Uniform::<f64>::new_percent(50.0, 150.0);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_float_new_centered_zero_width() {
+        Uniform::<f64>::new_centered(1.0, 0.0);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_floats_ulps_not_evenly_spaced() {
+        use distributions::uniform::UniformUlps;
+        UniformUlps::<f64>::new(0.0, 1.0);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_as_exclusive_empty() {
+        use distributions::uniform::{UniformSampler, UniformInt};
+        UniformInt::<u32>::new_inclusive(7, 7).as_exclusive();
     }
 }
@@ -162,3 +162,47 @@ macro_rules! simd_less_then {
 #[cfg(feature="simd_support")] simd_less_then! { f64x2 }
 #[cfg(feature="simd_support")] simd_less_then! { f64x4 }
 #[cfg(feature="simd_support")] simd_less_then! { f64x8 }
+
+
+/// Conversion between floats and integers such that consecutive integers
+/// correspond to consecutive (representable) floats. Where floats are evenly
+/// spaced, i.e. for subnormals and the smallest normal exponent, sampling
+/// these integers uniformly samples the floats uniformly.
+pub trait FloatAsOrderedInt: Sized {
+    /// Whether all floats in the range `[low, high]` are evenly spaced.
+    /// Requires `low <= high`.
+    fn evenly_spaced(low: Self, high: Self) -> bool;
+    fn to_ordered_int(self) -> i64;
+    fn from_ordered_int(i: i64) -> Self;
+}
+
+macro_rules! float_as_ordered_int_impl {
+    ($ty:ident, $uty:ident, $sign:expr) => {
+        impl FloatAsOrderedInt for $ty {
+            fn evenly_spaced(low: Self, high: Self) -> bool {
+                let limit = 2.0 * ::core::$ty::MIN_POSITIVE;
+                -limit <= low && high <= limit
+            }
+
+            fn to_ordered_int(self) -> i64 {
+                let bits = self.to_bits();
+                if bits & $sign != 0 {
+                    -((bits & !$sign) as i64)
+                } else {
+                    bits as i64
+                }
+            }
+
+            fn from_ordered_int(i: i64) -> Self {
+                if i < 0 {
+                    $ty::from_bits((-i) as $uty | $sign)
+                } else {
+                    $ty::from_bits(i as $uty)
+                }
+            }
+        }
+    }
+}
+
+float_as_ordered_int_impl! { f32, u32, 1 << 31 }
+float_as_ordered_int_impl! { f64, u64, 1 << 63 }
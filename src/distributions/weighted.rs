@@ -10,7 +10,7 @@
 
 use Rng;
 use distributions::Distribution;
-use distributions::uniform::{UniformSampler, SampleUniform, SampleBorrow};
+use distributions::uniform::{UniformSampler, SampleUniform, SampleBorrow, UniformInt};
 use ::core::cmp::PartialOrd;
 use ::{Error, ErrorKind};
 
@@ -109,6 +109,128 @@ impl<X> Distribution<usize> for WeightedIndex<X> where
     }
 }
 
+/// A weighted index distribution supporting cheap updates of the weights.
+///
+/// Like [`WeightedIndex`], sampling returns an index with a probability
+/// proportional to its weight. The weights are stored in a Fenwick tree
+/// (binary indexed tree), so both [`update_weight`] and sampling take
+/// `O(log n)` time, where rebuilding a `WeightedIndex` takes `O(n)`. This is
+/// useful when the weights change frequently, e.g. when balancing work across
+/// shards by their current load.
+///
+/// # Example
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand::distributions::DynamicWeightedIndex;
+///
+/// let mut shards = DynamicWeightedIndex::new(&[3, 1, 1]).unwrap();
+/// let chosen = shards.sample(&mut thread_rng());
+/// assert!(chosen < 3);
+/// // Shard 0 is now busy.
+/// shards.update_weight(0, 0).unwrap();
+/// assert_ne!(shards.sample(&mut thread_rng()), 0);
+/// ```
+///
+/// [`WeightedIndex`]: struct.WeightedIndex.html
+/// [`update_weight`]: #method.update_weight
+#[derive(Debug, Clone)]
+pub struct DynamicWeightedIndex {
+    weights: Vec<u64>,
+    tree: Vec<u64>,
+    total_weight: u64,
+}
+
+impl DynamicWeightedIndex {
+    /// Creates a new `DynamicWeightedIndex` from the given weights.
+    ///
+    /// Returns an error if the iterator is empty, if all weights are zero, or
+    /// if the total weight overflows a `u64`.
+    pub fn new<I>(weights: I) -> Result<DynamicWeightedIndex, Error>
+        where I: IntoIterator,
+              I::Item: SampleBorrow<u64>
+    {
+        let weights: Vec<u64> = weights.into_iter().map(|w| *w.borrow()).collect();
+        if weights.is_empty() {
+            return Err(Error::new(ErrorKind::Unexpected, "Empty iterator in DynamicWeightedIndex::new"));
+        }
+        let mut total_weight = 0u64;
+        for &w in weights.iter() {
+            total_weight = total_weight.checked_add(w).ok_or_else(||
+                Error::new(ErrorKind::Unexpected, "Total weight overflows in DynamicWeightedIndex::new"))?;
+        }
+        if total_weight == 0 {
+            return Err(Error::new(ErrorKind::Unexpected, "Total weight is zero in DynamicWeightedIndex::new"));
+        }
+
+        // Build the tree in linear time: each node adds itself to its parent.
+        let mut tree = weights.clone();
+        for i in 1..(tree.len() + 1) {
+            let parent = i + (i & i.wrapping_neg());
+            if parent <= tree.len() {
+                tree[parent - 1] += tree[i - 1];
+            }
+        }
+        Ok(DynamicWeightedIndex { weights: weights, tree: tree, total_weight: total_weight })
+    }
+
+    /// The current weight of index `i`. Panics if `i` is out of bounds.
+    pub fn weight(&self, i: usize) -> u64 {
+        self.weights[i]
+    }
+
+    /// The sum of all weights.
+    pub fn total_weight(&self) -> u64 {
+        self.total_weight
+    }
+
+    /// Set the weight of index `i` to `new_weight`, in `O(log n)` time.
+    ///
+    /// Returns an error, leaving the weights unchanged, if this would make
+    /// all weights zero or overflow the total weight. Panics if `i` is out of
+    /// bounds.
+    pub fn update_weight(&mut self, i: usize, new_weight: u64) -> Result<(), Error> {
+        let old_weight = self.weights[i];
+        let total_weight = (self.total_weight - old_weight).checked_add(new_weight)
+            .ok_or_else(|| Error::new(ErrorKind::Unexpected,
+                                      "Total weight overflows in DynamicWeightedIndex::update_weight"))?;
+        if total_weight == 0 {
+            return Err(Error::new(ErrorKind::Unexpected,
+                                  "Total weight is zero in DynamicWeightedIndex::update_weight"));
+        }
+        self.weights[i] = new_weight;
+        self.total_weight = total_weight;
+        let mut node = i + 1;
+        while node <= self.tree.len() {
+            // Wrapping arithmetic, as the node's sum never drops below the
+            // weight being removed.
+            self.tree[node - 1] = self.tree[node - 1].wrapping_sub(old_weight)
+                                                     .wrapping_add(new_weight);
+            node += node & node.wrapping_neg();
+        }
+        Ok(())
+    }
+}
+
+impl Distribution<usize> for DynamicWeightedIndex {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let mut chosen_weight = UniformInt::<u64>::sample_single(0, self.total_weight, rng);
+        // Descend the tree to find the first index whose cumulative weight is
+        // *higher* than the chosen weight.
+        let n = self.tree.len();
+        let mut pos = 0;
+        let mut step = n.next_power_of_two();
+        while step > 0 {
+            if pos + step <= n && self.tree[pos + step - 1] <= chosen_weight {
+                pos += step;
+                chosen_weight -= self.tree[pos - 1];
+            }
+            step >>= 1;
+        }
+        pos
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -167,4 +289,53 @@ mod test {
         assert!(WeightedIndex::new(&[-10, 20, 1, 30]).is_err());
         assert!(WeightedIndex::new(&[-10]).is_err());
     }
+
+    #[test]
+    fn test_dynamic_weighted_index() {
+        let mut r = ::test::rng(701);
+        const N_REPS: u64 = 10_000;
+
+        fn verify<R: Rng>(distr: &DynamicWeightedIndex, r: &mut R) {
+            let mut chosen = [0u64; 7];
+            for _ in 0..N_REPS {
+                chosen[distr.sample(r)] += 1;
+            }
+            for (i, &count) in chosen.iter().enumerate() {
+                let exp = (distr.weight(i) * N_REPS) as f64 / distr.total_weight() as f64;
+                if exp == 0.0 {
+                    assert_eq!(count, 0);
+                } else {
+                    assert!((count as f64 - exp).abs() / exp <= 0.25);
+                }
+            }
+        }
+
+        let mut distr = DynamicWeightedIndex::new(&[1, 2, 3, 0, 5, 6, 7]).unwrap();
+        assert_eq!(distr.total_weight(), 24);
+        verify(&distr, &mut r);
+
+        distr.update_weight(6, 0).unwrap();
+        distr.update_weight(3, 4).unwrap();
+        distr.update_weight(0, 10).unwrap();
+        assert_eq!(distr.total_weight(), 30);
+        verify(&distr, &mut r);
+
+        distr.update_weight(6, 1).unwrap();
+        for i in 0..6 {
+            distr.update_weight(i, 0).unwrap();
+        }
+        assert_eq!(distr.total_weight(), 1);
+        assert!(distr.update_weight(6, 0).is_err());
+        assert_eq!(distr.weight(6), 1);
+        for _ in 0..5 {
+            assert_eq!(distr.sample(&mut r), 6);
+        }
+
+        assert!(DynamicWeightedIndex::new(&[10][0..0]).is_err());
+        assert!(DynamicWeightedIndex::new(&[0, 0]).is_err());
+        assert!(DynamicWeightedIndex::new(&[::core::u64::MAX, 1]).is_err());
+        let mut distr = DynamicWeightedIndex::new(&[::core::u64::MAX, 0]).unwrap();
+        assert!(distr.update_weight(1, 1).is_err());
+        assert_eq!(distr.sample(&mut r), 0);
+    }
 }